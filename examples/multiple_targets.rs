@@ -14,7 +14,7 @@ fn main() {
         })
         .insert_resource(PlayerSettings {
             pos: Vec3::new(2., 0., 0.),
-            player_asset: "models/craft_speederA.glb#Scene0",
+            player_asset: "models/craft_speederA.glb#Scene0".into(),
             ..Default::default()
         })
         .add_startup_system(setup)
@@ -67,6 +67,7 @@ fn setup(
     });
 }
 
+#[allow(clippy::type_complexity)]
 fn set_closest_target(
     mut cl: ResMut<CamLogic>,
     mut transforms: Query<(&PlayerMove, &Transform)>,
@@ -0,0 +1,65 @@
+//Base
+use bevy::prelude::*;
+use bevy_config_cam::*;
+
+fn main() {
+    App::new()
+        .insert_resource(Msaa { samples: 4 })
+        .add_plugins(DefaultPlugins)
+        .add_plugin(ConfigCam)
+        .insert_resource(PlayerSettings {
+            pos: Vec3::new(2., 0., 0.),
+            player_asset: "models/craft_speederA.glb#Scene0".into(),
+            ..Default::default()
+        })
+        .insert_resource(CamPresets {
+            presets: vec![
+                CamPreset {
+                    keys: vec![KeyCode::Key1],
+                    ..CamPreset::new("gameplay", CameraState::FollowBehind)
+                },
+                CamPreset {
+                    fov: 0.4,
+                    framing: Some(Framing {
+                        dead_zone: Vec2::new(0.3, 0.2),
+                        soft_zone: Vec2::new(0.7, 0.6),
+                        damping: 0.5,
+                    }),
+                    keys: vec![KeyCode::Key2],
+                    ..CamPreset::new("cinematic", CameraState::LookAt)
+                },
+                CamPreset {
+                    fov: 1.2,
+                    movement: PresetMovement {
+                        speed: 30.,
+                        ..Default::default()
+                    },
+                    keys: vec![KeyCode::Key3],
+                    ..CamPreset::new("debug", CameraState::Free)
+                },
+            ],
+            blend: 1.5,
+        })
+        .add_startup_system(setup)
+        .run();
+}
+
+/// set up a simple 3D scene
+fn setup(
+    mut commands: Commands,
+    mut meshes: ResMut<Assets<Mesh>>,
+    mut materials: ResMut<Assets<StandardMaterial>>,
+) {
+    // plane
+    commands.spawn_bundle(PbrBundle {
+        mesh: meshes.add(Mesh::from(shape::Plane { size: 11.0 })),
+        material: materials.add(Color::rgb(0.3, 0.5, 0.3).into()),
+        ..Default::default()
+    });
+
+    // light
+    commands.spawn_bundle(PointLightBundle {
+        transform: Transform::from_xyz(4.0, 8.0, 4.0),
+        ..Default::default()
+    });
+}
@@ -0,0 +1,42 @@
+//Base
+use bevy::{asset::AssetServerSettings, prelude::*};
+use bevy_config_cam::*;
+
+fn main() {
+    App::new()
+        .insert_resource(Msaa { samples: 4 })
+        // Reload the profile when it is changed on disk
+        .insert_resource(AssetServerSettings {
+            watch_for_changes: true,
+            ..Default::default()
+        })
+        .add_plugins(DefaultPlugins)
+        .add_plugin(ConfigCam)
+        .add_startup_system(setup)
+        .run();
+}
+
+/// set up a simple 3D scene and load the camera profile
+fn setup(
+    mut commands: Commands,
+    asset_server: Res<AssetServer>,
+    mut meshes: ResMut<Assets<Mesh>>,
+    mut materials: ResMut<Assets<StandardMaterial>>,
+) {
+    commands.insert_resource(ActiveCamProfile(
+        asset_server.load("profiles/default.camprofile.ron"),
+    ));
+
+    // plane
+    commands.spawn_bundle(PbrBundle {
+        mesh: meshes.add(Mesh::from(shape::Plane { size: 5.0 })),
+        material: materials.add(Color::rgb(0.3, 0.5, 0.3).into()),
+        ..Default::default()
+    });
+
+    // light
+    commands.spawn_bundle(PointLightBundle {
+        transform: Transform::from_xyz(4.0, 8.0, 4.0),
+        ..Default::default()
+    });
+}
@@ -14,7 +14,7 @@ fn main() {
         })
         .insert_resource(PlayerSettings {
             pos: Vec3::new(2., 0., 0.),
-            player_asset: "models/craft_speederA.glb#Scene0",
+            player_asset: "models/craft_speederA.glb#Scene0".into(),
             ..Default::default()
         })
         .add_startup_system(setup)
@@ -3,8 +3,7 @@ use bevy::{
     render::camera::{ActiveCamera, Camera3d},
 };
 
-use bevy_config_cam::next_enum;
-use strum::IntoEnumIterator;
+use bevy_config_cam::Cycle;
 use strum_macros::EnumIter;
 
 // Used in queries when you want filter between cameras
@@ -86,7 +85,7 @@ fn cycle_camera_state(
     mut keys: ResMut<Input<KeyCode>>,
 ) {
     if keys.just_pressed(KeyCode::E) {
-        let new_cam_state = next_enum!(SwitchableCameras, selected_cam);
+        let new_cam_state = selected_cam.current().next();
         println!("New camera: {:?}", new_cam_state);
         selected_cam.set(new_cam_state).unwrap();
 
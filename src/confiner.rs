@@ -0,0 +1,124 @@
+use bevy::prelude::*;
+
+/// Area on the XZ plane the camera is confined to, `Vec2::y` maps to the world Z axis
+#[derive(Clone, Debug, PartialEq)]
+pub enum ConfinerShape {
+    Box {
+        min: Vec2,
+        max: Vec2,
+    },
+    /// Convex polygon, the points can be in either winding order
+    ///
+    /// Fewer than 3 points enclose nothing, the camera is then held on them,
+    /// and an empty polygon only confines the height.
+    Polygon(Vec<Vec2>),
+}
+
+impl ConfinerShape {
+    pub fn contains(&self, point: Vec2) -> bool {
+        match self {
+            ConfinerShape::Box { min, max } => point.cmpge(*min).all() && point.cmple(*max).all(),
+            ConfinerShape::Polygon(points) if points.len() < 3 => false,
+            ConfinerShape::Polygon(points) => {
+                let mut sign = 0.0;
+                for (a, b) in edges(points) {
+                    let cross = (b - a).perp_dot(point - a);
+                    if cross != 0.0 {
+                        if sign != 0.0 && cross.signum() != sign {
+                            return false;
+                        }
+                        sign = cross.signum();
+                    }
+                }
+                true
+            }
+        }
+    }
+
+    /// Returns the closest point to `point` inside the shape
+    pub fn clamp(&self, point: Vec2) -> Vec2 {
+        match self {
+            ConfinerShape::Box { min, max } => point.clamp(*min, *max),
+            ConfinerShape::Polygon(points) => {
+                if points.is_empty() || self.contains(point) {
+                    return point;
+                }
+                edges(points)
+                    .map(|(a, b)| {
+                        let edge = b - a;
+                        let t = if edge.length_squared() > 0.0 {
+                            ((point - a).dot(edge) / edge.length_squared()).clamp(0.0, 1.0)
+                        } else {
+                            0.0
+                        };
+                        a + edge * t
+                    })
+                    .min_by(|a, b| {
+                        a.distance_squared(point)
+                            .total_cmp(&b.distance_squared(point))
+                    })
+                    .unwrap_or(point)
+            }
+        }
+    }
+}
+
+fn edges(points: &[Vec2]) -> impl Iterator<Item = (Vec2, Vec2)> + '_ {
+    points
+        .iter()
+        .zip(points.iter().cycle().skip(1))
+        .map(|(a, b)| (*a, *b))
+}
+
+/// Keeps the free and top-down camera inside the playable area, insert it as a resource to enable it
+#[derive(Clone, Debug)]
+pub struct CameraConfiner {
+    pub shape: ConfinerShape,
+    pub min_height: f32,
+    pub max_height: f32,
+    /// When set, the camera is pulled back inside with this damping per second instead of stopping at the edge
+    pub damping: Option<f32>,
+}
+
+impl Default for CameraConfiner {
+    fn default() -> Self {
+        Self {
+            shape: ConfinerShape::Box {
+                min: Vec2::splat(-50.),
+                max: Vec2::splat(50.),
+            },
+            min_height: 0.,
+            max_height: 50.,
+            damping: None,
+        }
+    }
+}
+
+impl CameraConfiner {
+    pub fn contains(&self, point: Vec3) -> bool {
+        (self.min_height..=self.max_height).contains(&point.y)
+            && self.shape.contains(Vec2::new(point.x, point.z))
+    }
+
+    pub fn clamp(&self, point: Vec3) -> Vec3 {
+        let xz = self.shape.clamp(Vec2::new(point.x, point.z));
+        Vec3::new(
+            xz.x,
+            point
+                .y
+                .clamp(self.min_height, self.max_height.max(self.min_height)),
+            xz.y,
+        )
+    }
+
+    /// Confines the camera moving from `from` to `to` this frame
+    pub fn confine(&self, from: Vec3, to: Vec3, delta_seconds: f32) -> Vec3 {
+        if self.contains(to) {
+            return to;
+        }
+        match self.damping {
+            Some(damping) => from.lerp(self.clamp(to), (damping * delta_seconds).clamp(0.0, 1.0)),
+            None => self.clamp(to),
+        }
+    }
+}
@@ -0,0 +1,107 @@
+use bevy::{ecs::schedule::ShouldRun, prelude::*};
+
+use crate::{CameraModeChanged, CameraState, Cycle, ScrollType, ScrollTypeChanged};
+
+/// Owns the camera mode and the setting adjusted by the mouse-scroll
+///
+/// Every change made through it is reported with a `CameraModeChanged` or `ScrollTypeChanged` event.
+pub struct CamController {
+    mode: CameraState,
+    scroll_type: ScrollType,
+    reported_mode: CameraState,
+    reported_scroll_type: ScrollType,
+}
+
+impl Default for CamController {
+    fn default() -> Self {
+        Self::new(CameraState::default())
+    }
+}
+
+impl CamController {
+    pub fn new(mode: CameraState) -> Self {
+        Self {
+            mode: mode.clone(),
+            scroll_type: ScrollType::MovementSpeed,
+            reported_mode: mode,
+            reported_scroll_type: ScrollType::MovementSpeed,
+        }
+    }
+
+    pub fn mode(&self) -> &CameraState {
+        &self.mode
+    }
+
+    pub fn set_mode(&mut self, mode: CameraState) {
+        self.mode = mode;
+    }
+
+    pub fn next_mode(&mut self) {
+        self.mode = self.mode.next();
+    }
+
+    pub fn previous_mode(&mut self) {
+        self.mode = self.mode.prev();
+    }
+
+    pub fn scroll_type(&self) -> &ScrollType {
+        &self.scroll_type
+    }
+
+    pub fn set_scroll_type(&mut self, scroll_type: ScrollType) {
+        self.scroll_type = scroll_type;
+    }
+
+    pub fn next_scroll_type(&mut self) {
+        self.scroll_type = self.scroll_type.next();
+    }
+
+    pub fn previous_scroll_type(&mut self) {
+        self.scroll_type = self.scroll_type.prev();
+    }
+}
+
+/// Run criteria for gating a `SystemSet` on the camera mode
+///
+/// ```
+/// # use bevy::prelude::*;
+/// # use bevy_config_cam::*;
+/// # fn free_cam_ui() {}
+/// App::new().add_system_set(
+///     SystemSet::new()
+///         .with_run_criteria(in_camera_mode(CameraState::Free))
+///         .with_system(free_cam_ui),
+/// );
+/// ```
+pub fn in_camera_mode(
+    mode: CameraState,
+) -> impl FnMut(Res<CamController>) -> ShouldRun + Send + Sync + 'static {
+    move |controller: Res<CamController>| {
+        if *controller.mode() == mode {
+            ShouldRun::Yes
+        } else {
+            ShouldRun::No
+        }
+    }
+}
+
+pub(crate) fn report_changes(
+    mut controller: ResMut<CamController>,
+    mut modes: EventWriter<CameraModeChanged>,
+    mut scroll_types: EventWriter<ScrollTypeChanged>,
+) {
+    if controller.mode != controller.reported_mode {
+        modes.send(CameraModeChanged {
+            old: controller.reported_mode.clone(),
+            new: controller.mode.clone(),
+        });
+        controller.reported_mode = controller.mode.clone();
+    }
+    if controller.scroll_type != controller.reported_scroll_type {
+        scroll_types.send(ScrollTypeChanged {
+            old: controller.reported_scroll_type.clone(),
+            new: controller.scroll_type.clone(),
+        });
+        controller.reported_scroll_type = controller.scroll_type.clone();
+    }
+}
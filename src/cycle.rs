@@ -0,0 +1,61 @@
+use strum::IntoEnumIterator;
+
+/// Steps through the variants of an enum, wrapping around at either end
+///
+/// Implemented for every enum deriving `strum_macros::EnumIter` and `PartialEq`.
+///
+/// ```
+/// # use bevy_config_cam::*;
+/// assert_eq!(CameraState::Free.next(), CameraState::LookAt);
+/// assert_eq!(CameraState::LookAt.prev(), CameraState::Free);
+/// assert_eq!(
+///     CameraState::LookAt.next_where(|mode| *mode != CameraState::FollowStatic),
+///     Some(CameraState::TopDown)
+/// );
+/// ```
+pub trait Cycle: Sized {
+    fn next(&self) -> Self;
+    fn prev(&self) -> Self;
+    /// The next variant matching `predicate`, skipping the others, or `None` if no other variant matches
+    fn next_where(&self, predicate: impl FnMut(&Self) -> bool) -> Option<Self>;
+    /// The previous variant matching `predicate`, skipping the others, or `None` if no other variant matches
+    fn prev_where(&self, predicate: impl FnMut(&Self) -> bool) -> Option<Self>;
+}
+
+fn step_where<T: IntoEnumIterator + PartialEq>(
+    value: &T,
+    forward: bool,
+    mut predicate: impl FnMut(&T) -> bool,
+) -> Option<T> {
+    let mut variants: Vec<T> = T::iter().collect();
+    let count = variants.len();
+    let index = variants.iter().position(|v| v == value).unwrap_or(0);
+    let found = (1..count)
+        .map(|offset| {
+            if forward {
+                (index + offset) % count
+            } else {
+                (index + count - offset) % count
+            }
+        })
+        .find(|i| predicate(&variants[*i]))?;
+    Some(variants.swap_remove(found))
+}
+
+impl<T: IntoEnumIterator + PartialEq> Cycle for T {
+    fn next(&self) -> Self {
+        step_where(self, true, |_| true).unwrap_or_else(|| T::iter().next().unwrap())
+    }
+
+    fn prev(&self) -> Self {
+        step_where(self, false, |_| true).unwrap_or_else(|| T::iter().next().unwrap())
+    }
+
+    fn next_where(&self, predicate: impl FnMut(&Self) -> bool) -> Option<Self> {
+        step_where(self, true, predicate)
+    }
+
+    fn prev_where(&self, predicate: impl FnMut(&Self) -> bool) -> Option<Self> {
+        step_where(self, false, predicate)
+    }
+}
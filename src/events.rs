@@ -0,0 +1,49 @@
+use bevy::prelude::*;
+
+use crate::{CameraState, MovementSettings, ScrollType};
+
+/// Sent when the camera mode is switched
+#[derive(Clone, Debug, PartialEq)]
+pub struct CameraModeChanged {
+    pub old: CameraState,
+    pub new: CameraState,
+}
+
+/// Sent when the setting adjusted by the mouse-scroll is switched
+#[derive(Clone, Debug, PartialEq)]
+pub struct ScrollTypeChanged {
+    pub old: ScrollType,
+    pub new: ScrollType,
+}
+
+/// Sent when a setting is adjusted with the mouse-scroll
+///
+/// `Zoom` reports the field of view in radians or the camera distance, depending on the camera mode,
+/// and `CamFwd` reports `0.0` or `1.0`.
+#[derive(Clone, Debug, PartialEq)]
+pub struct CameraSettingChanged {
+    pub setting: ScrollType,
+    pub old: f32,
+    pub new: f32,
+}
+
+/// Logs the camera events through bevy's log facade when `MovementSettings::log` is set
+pub(crate) fn log_changes(
+    settings: Res<MovementSettings>,
+    mut modes: EventReader<CameraModeChanged>,
+    mut scroll_types: EventReader<ScrollTypeChanged>,
+    mut changes: EventReader<CameraSettingChanged>,
+) {
+    if !settings.log {
+        return;
+    }
+    for event in modes.iter() {
+        info!("Camera: {:?}", event.new);
+    }
+    for event in scroll_types.iter() {
+        info!("Setting: {:?}", event.new);
+    }
+    for event in changes.iter() {
+        info!("{:?}: {:?}", event.setting, event.new);
+    }
+}
@@ -0,0 +1,117 @@
+use bevy::{prelude::*, utils::HashMap};
+
+use crate::CameraState;
+
+/// Screen-space framing used by the camera modes that track a focus point
+/// (`LookAt`, `FollowStatic` and `TopDown`).
+///
+/// All zones are half-extents in normalized screen space, where `1.0` is the edge of the screen.
+/// While the target stays inside the dead zone the camera does not react, inside the soft zone
+/// the camera catches up using `damping` and the target is never allowed past the soft zone.
+#[derive(Clone, Copy, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Framing {
+    pub dead_zone: Vec2,
+    pub soft_zone: Vec2,
+    /// How fast the camera catches up with the target inside the soft zone, per second
+    pub damping: f32,
+}
+
+impl Default for Framing {
+    fn default() -> Self {
+        Self {
+            dead_zone: Vec2::splat(0.05),
+            soft_zone: Vec2::splat(0.8),
+            damping: 2.0,
+        }
+    }
+}
+
+impl Framing {
+    /// Keeps the target in the center of the screen at all times
+    pub fn locked() -> Self {
+        Self {
+            dead_zone: Vec2::ZERO,
+            soft_zone: Vec2::ZERO,
+            damping: f32::INFINITY,
+        }
+    }
+
+    fn damp(&self, delta_seconds: f32) -> f32 {
+        if self.damping.is_infinite() {
+            1.0
+        } else {
+            1.0 - (-self.damping * delta_seconds).exp()
+        }
+    }
+
+    fn correct_axis(&self, offset: f32, dead: f32, soft: f32, damp: f32) -> f32 {
+        let soft = soft.max(dead);
+        let dist = offset.abs();
+        if dist <= dead {
+            return 0.0;
+        }
+        let hard = (dist - soft).max(0.0);
+        let smooth = (dist.min(soft) - dead) * damp;
+        offset.signum() * (hard + smooth)
+    }
+
+    /// Returns the new focus point of a camera currently looking at `is_focus`
+    /// that should be looking at `should_focus`
+    pub fn frame(
+        &self,
+        camera: &Transform,
+        projection: &PerspectiveProjection,
+        is_focus: Vec3,
+        should_focus: Vec3,
+        delta_seconds: f32,
+    ) -> Vec3 {
+        let offset = should_focus - is_focus;
+        let damp = self.damp(delta_seconds);
+
+        let forward = camera.forward();
+        let depth = (should_focus - camera.translation).dot(forward);
+        if depth <= f32::EPSILON {
+            // Target is behind the camera, screen space is meaningless so just catch up
+            return is_focus + offset * damp;
+        }
+
+        let half_height = depth * (projection.fov * 0.5).tan();
+        let half_width = half_height * projection.aspect_ratio;
+
+        let (right, up) = (camera.right(), camera.up());
+        let screen = Vec2::new(offset.dot(right) / half_width, offset.dot(up) / half_height);
+        let correction = Vec2::new(
+            self.correct_axis(screen.x, self.dead_zone.x, self.soft_zone.x, damp),
+            self.correct_axis(screen.y, self.dead_zone.y, self.soft_zone.y, damp),
+        );
+
+        is_focus
+            + right * correction.x * half_width
+            + up * correction.y * half_height
+            + forward * offset.dot(forward) * damp
+    }
+}
+
+/// Per camera mode framing, modes without an entry use `default`
+pub struct CameraFraming {
+    pub default: Framing,
+    pub modes: HashMap<CameraState, Framing>,
+}
+
+impl Default for CameraFraming {
+    fn default() -> Self {
+        let mut modes = HashMap::default();
+        modes.insert(CameraState::TopDown, Framing::locked());
+        Self {
+            default: Framing::default(),
+            modes,
+        }
+    }
+}
+
+impl CameraFraming {
+    pub fn get(&self, state: &CameraState) -> &Framing {
+        self.modes.get(state).unwrap_or(&self.default)
+    }
+}
@@ -0,0 +1,208 @@
+use std::{f32::consts::FRAC_PI_4, sync::Arc};
+
+use bevy::{
+    prelude::*,
+    render::mesh::{Indices, PrimitiveTopology, VertexAttributeValues},
+};
+
+/// A surface the player stands on, sampled from above at points on the XZ plane
+#[derive(Clone)]
+pub enum Ground {
+    /// Height of the ground at a point on the XZ plane
+    Height(Arc<dyn Fn(Vec2) -> f32 + Send + Sync>),
+    /// Triangles of a heightmap mesh in world space
+    Mesh(MeshGround),
+}
+
+impl Ground {
+    pub fn from_fn(height: impl Fn(Vec2) -> f32 + Send + Sync + 'static) -> Self {
+        Ground::Height(Arc::new(height))
+    }
+
+    /// The triangles of `mesh` placed with `transform`, `None` if it is not a triangle list with positions
+    pub fn from_mesh(mesh: &Mesh, transform: &Transform) -> Option<Self> {
+        if mesh.primitive_topology() != PrimitiveTopology::TriangleList {
+            return None;
+        }
+        let positions = match mesh.attribute(Mesh::ATTRIBUTE_POSITION)? {
+            VertexAttributeValues::Float32x3(positions) => positions,
+            _ => return None,
+        };
+        let matrix = transform.compute_matrix();
+        let vertices: Vec<Vec3> = positions
+            .iter()
+            .map(|p| matrix.transform_point3(Vec3::from(*p)))
+            .collect();
+        let indices: Vec<usize> = match mesh.indices() {
+            Some(Indices::U16(indices)) => indices.iter().map(|i| *i as usize).collect(),
+            Some(Indices::U32(indices)) => indices.iter().map(|i| *i as usize).collect(),
+            None => (0..vertices.len()).collect(),
+        };
+        let triangles = indices
+            .chunks_exact(3)
+            .filter_map(|t| {
+                Some([
+                    *vertices.get(t[0])?,
+                    *vertices.get(t[1])?,
+                    *vertices.get(t[2])?,
+                ])
+            })
+            .collect();
+        Some(Ground::Mesh(MeshGround::new(triangles)))
+    }
+
+    /// Height of the ground at `point`, `None` past the edge of a mesh
+    pub fn height(&self, point: Vec2) -> Option<f32> {
+        match self {
+            Ground::Height(height) => Some(height(point)),
+            Ground::Mesh(mesh) => mesh
+                .triangles_at(point)
+                .filter_map(|triangle| sample(triangle, point).map(|(height, _)| height))
+                .reduce(f32::max),
+        }
+    }
+
+    /// Upwards facing normal of the ground at `point`, `None` past the edge of a mesh
+    pub fn normal(&self, point: Vec2) -> Option<Vec3> {
+        match self {
+            Ground::Height(height) => {
+                let e = 0.05;
+                let dx = height(point + Vec2::X * e) - height(point - Vec2::X * e);
+                let dz = height(point + Vec2::Y * e) - height(point - Vec2::Y * e);
+                Some(Vec3::new(-dx, 2. * e, -dz).normalize())
+            }
+            Ground::Mesh(mesh) => mesh
+                .triangles_at(point)
+                .filter_map(|triangle| sample(triangle, point))
+                .reduce(|a, b| if b.0 > a.0 { b } else { a })
+                .map(|(_, normal)| normal),
+        }
+    }
+}
+
+/// Triangles of a mesh bucketed on a grid over the XZ plane, so a query only tests the triangles near it
+#[derive(Clone)]
+pub struct MeshGround {
+    triangles: Vec<[Vec3; 3]>,
+    min: Vec2,
+    cell_size: f32,
+    columns: usize,
+    rows: usize,
+    /// Indices into `triangles` of the triangles overlapping each cell, row by row
+    cells: Vec<Vec<usize>>,
+}
+
+impl MeshGround {
+    pub fn new(triangles: Vec<[Vec3; 3]>) -> Self {
+        let bounds = |triangle: &[Vec3; 3]| {
+            let points = triangle.map(|p| Vec2::new(p.x, p.z));
+            (
+                points[0].min(points[1]).min(points[2]),
+                points[0].max(points[1]).max(points[2]),
+            )
+        };
+        let (min, max) = triangles
+            .iter()
+            .map(bounds)
+            .reduce(|(a_min, a_max), (b_min, b_max)| (a_min.min(b_min), a_max.max(b_max)))
+            .unwrap_or((Vec2::ZERO, Vec2::ZERO));
+
+        // Around one triangle per cell on a square mesh
+        let extent = max - min;
+        let per_side = (triangles.len() as f32).sqrt().ceil().max(1.);
+        let cell_size = (extent.max_element() / per_side).max(1e-3);
+        let columns = (extent.x / cell_size) as usize + 1;
+        let rows = (extent.y / cell_size) as usize + 1;
+
+        let mut cells = vec![Vec::new(); columns * rows];
+        for (i, triangle) in triangles.iter().enumerate() {
+            let (t_min, t_max) = bounds(triangle);
+            let (first, last) = (
+                ((t_min - min) / cell_size).as_uvec2(),
+                ((t_max - min) / cell_size).as_uvec2(),
+            );
+            for row in first.y as usize..=(last.y as usize).min(rows - 1) {
+                for column in first.x as usize..=(last.x as usize).min(columns - 1) {
+                    cells[row * columns + column].push(i);
+                }
+            }
+        }
+
+        Self {
+            triangles,
+            min,
+            cell_size,
+            columns,
+            rows,
+            cells,
+        }
+    }
+
+    pub fn triangles(&self) -> &[[Vec3; 3]] {
+        &self.triangles
+    }
+
+    /// Triangles that may be above or below `point`
+    fn triangles_at(&self, point: Vec2) -> impl Iterator<Item = &[Vec3; 3]> {
+        let cell = ((point - self.min) / self.cell_size).floor();
+        let index = (cell.min_element() >= 0.
+            && (cell.x as usize) < self.columns
+            && (cell.y as usize) < self.rows)
+            .then(|| cell.y as usize * self.columns + cell.x as usize);
+        index
+            .map_or(&[][..], |index| &self.cells[index][..])
+            .iter()
+            .map(|i| &self.triangles[*i])
+    }
+}
+
+/// Height and upwards normal of the triangle above or below `point`
+fn sample([a, b, c]: &[Vec3; 3], point: Vec2) -> Option<(f32, Vec3)> {
+    let d = (b.z - c.z) * (a.x - c.x) + (c.x - b.x) * (a.z - c.z);
+    if d.abs() < f32::EPSILON {
+        return None;
+    }
+    let u = ((b.z - c.z) * (point.x - c.x) + (c.x - b.x) * (point.y - c.z)) / d;
+    let v = ((c.z - a.z) * (point.x - c.x) + (a.x - c.x) * (point.y - c.z)) / d;
+    let w = 1. - u - v;
+    let e = -1e-5;
+    if u < e || v < e || w < e {
+        return None;
+    }
+    let normal = (*b - *a).cross(*c - *a).normalize();
+    let normal = if normal.y < 0. { -normal } else { normal };
+    Some((u * a.y + v * b.y + w * c.y, normal))
+}
+
+/// Insert to have the player stand on and follow a ground surface instead of the plane at height 0
+///
+/// Flying players are kept above it, grounded players walk on it and fall off its edges.
+#[derive(Clone)]
+pub struct PlayerGround {
+    pub ground: Ground,
+    /// Steepest slope in radians the player can move up onto
+    pub max_slope: f32,
+}
+
+impl PlayerGround {
+    pub fn new(ground: Ground) -> Self {
+        Self {
+            ground,
+            max_slope: FRAC_PI_4,
+        }
+    }
+
+    /// Whether the player can move from `from` onto the ground at `to`, going down is always allowed
+    pub fn walkable(&self, from: Vec2, to: Vec2) -> bool {
+        match (
+            self.ground.height(from),
+            self.ground.height(to),
+            self.ground.normal(to),
+        ) {
+            (Some(from_height), Some(to_height), Some(normal)) => {
+                to_height <= from_height || normal.angle_between(Vec3::Y) <= self.max_slope
+            }
+            _ => true,
+        }
+    }
+}
@@ -0,0 +1,140 @@
+use bevy::{prelude::*, render::camera::PerspectiveProjection};
+
+use crate::{
+    CamController, CamTime, CameraState, HideInPhotoMode, MainFlyCam, MovementSettings,
+    PlayerSettings, ScrollType,
+};
+
+/// Look of the on-screen overlay showing the camera mode and the selected setting
+pub struct HudSettings {
+    pub font: &'static str,
+    pub font_size: f32,
+    pub color: Color,
+    /// Seconds the overlay stays visible after something changed
+    pub delay: f32,
+    /// Seconds it takes the overlay to fade out after the delay
+    pub fade: f32,
+    /// Disable if your game already spawns a `UiCameraBundle`
+    pub spawn_ui_camera: bool,
+}
+
+impl Default for HudSettings {
+    fn default() -> Self {
+        Self {
+            font: "fonts/FiraSans-Bold.ttf",
+            font_size: 20.,
+            color: Color::WHITE,
+            delay: 3.,
+            fade: 1.,
+            spawn_ui_camera: true,
+        }
+    }
+}
+
+pub(crate) struct HudPlugin;
+impl Plugin for HudPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<HudSettings>()
+            .add_startup_system(setup_hud)
+            .add_system(update_hud);
+    }
+}
+
+#[derive(Component)]
+struct HudText;
+
+fn setup_hud(mut commands: Commands, asset_server: Res<AssetServer>, hud: Res<HudSettings>) {
+    if hud.spawn_ui_camera {
+        commands.spawn_bundle(UiCameraBundle::default());
+    }
+    commands
+        .spawn_bundle(TextBundle {
+            style: Style {
+                position_type: PositionType::Absolute,
+                position: Rect {
+                    top: Val::Px(5.0),
+                    left: Val::Px(5.0),
+                    ..Default::default()
+                },
+                ..Default::default()
+            },
+            text: Text::with_section(
+                "",
+                TextStyle {
+                    font: asset_server.load(hud.font),
+                    font_size: hud.font_size,
+                    color: hud.color,
+                },
+                Default::default(),
+            ),
+            ..Default::default()
+        })
+        .insert(HudText)
+        .insert(HideInPhotoMode);
+}
+
+fn keys(codes: &[KeyCode]) -> String {
+    codes
+        .iter()
+        .map(|code| format!("{:?}", code))
+        .collect::<Vec<_>>()
+        .join("/")
+}
+
+#[allow(clippy::too_many_arguments)]
+fn update_hud(
+    time: Res<CamTime>,
+    hud: Res<HudSettings>,
+    settings: Res<MovementSettings>,
+    p_settings: Res<PlayerSettings>,
+    controller: Res<CamController>,
+    projections: Query<&PerspectiveProjection, MainFlyCam>,
+    mut shown_at: Local<f64>,
+    mut query: Query<&mut Text, With<HudText>>,
+) {
+    let value = match controller.scroll_type() {
+        ScrollType::MovementSpeed => format!("{:.2}", settings.speed),
+        ScrollType::Zoom => match controller.mode() {
+            CameraState::TopDown | CameraState::TopDownDirection => {
+                format!("{:.2}", settings.dist)
+            }
+            CameraState::FollowBehind => format!("{:.2}", settings.follow_dist),
+            _ => projections
+                .get_single()
+                .map(|project| format!("{:.1}°", project.fov.to_degrees()))
+                .unwrap_or_default(),
+        },
+        ScrollType::Sensitivity => format!("{:.6}", settings.sensitivity),
+        ScrollType::Lerp => format!("{:.2}", settings.lerp),
+        ScrollType::CamFwd => format!("{}", p_settings.cam_fwd),
+    };
+    let content = format!(
+        "Camera: {:?}\n{:?}: {}\n[{}] Next camera  [{}] Next setting  [Scroll] Change setting",
+        controller.mode(),
+        controller.scroll_type(),
+        value,
+        keys(&settings.map.next_cam),
+        keys(&settings.map.next_setting),
+    );
+
+    let now = time.seconds_since_startup();
+    for mut text in query.iter_mut() {
+        let section = &mut text.sections[0];
+        if section.value != content {
+            section.value = content.clone();
+            *shown_at = now;
+        }
+
+        let faded = if hud.fade > 0. {
+            ((now - *shown_at) as f32 - hud.delay) / hud.fade
+        } else if (now - *shown_at) as f32 > hud.delay {
+            1.
+        } else {
+            0.
+        };
+        section
+            .style
+            .color
+            .set_a(hud.color.a() * (1. - faded.clamp(0., 1.)));
+    }
+}
@@ -1,4 +1,5 @@
 use bevy::{
+    core::CoreSystem,
     ecs::{
         event::{Events, ManualEventReader},
         schedule::SystemSet,
@@ -7,35 +8,64 @@ use bevy::{
     prelude::*,
     render::{
         camera::Camera,
-        camera::CameraProjection,
         camera::{ActiveCamera, Camera3d, PerspectiveProjection},
     },
+    utils::HashMap,
     window::Windows,
 };
+use std::f32::consts::{PI, TAU};
+use window::{cursor_state, window_id};
 
-use strum::IntoEnumIterator;
 use strum_macros::EnumIter;
 
+mod confiner;
+mod controller;
+mod cycle;
+mod events;
+mod framing;
+mod ground;
+#[cfg(feature = "hud")]
+mod hud;
+mod movement;
+mod multiplayer;
+#[cfg(feature = "serde")]
+mod persist;
+mod photo;
+mod preset;
+#[cfg(feature = "serde")]
+mod profile;
+mod scroll;
+mod time;
+mod window;
+mod zoom;
+pub use confiner::*;
+pub use controller::{in_camera_mode, CamController};
+pub use cycle::Cycle;
+pub use events::*;
+pub use framing::*;
+pub use ground::*;
+#[cfg(feature = "hud")]
+pub use hud::HudSettings;
+pub use movement::*;
+#[cfg(feature = "serde")]
+pub use persist::{CamConfig, CamConfigError, CamConfigFile};
+pub use photo::{
+    gameplay_running, HideInPhotoMode, PhotoMode, PhotoParams, PhotoSettings, TogglePhotoMode,
+};
+pub use preset::{CamPreset, CamPresets, PresetMovement, SwitchCamPreset};
+#[cfg(feature = "serde")]
+pub use profile::{ActiveCamProfile, CamProfile, CamProfileLoader};
+pub use scroll::*;
+pub use time::CamTime;
+pub use window::CamWindow;
+pub use zoom::ZoomSettings;
+
+/// Returns the variant after the current one of a `State<T>`
+#[deprecated(note = "use `Cycle::next` on the current state instead")]
 #[macro_export]
 macro_rules! next_enum {
     ($l:ident, $k:expr) => {
-        $l::iter()
-            .enumerate()
-            .nth(
-                $l::iter()
-                    .enumerate()
-                    .find(|a| a.1 == *$k.current())
-                    .map(|(i, _)| {
-                        if i + 1 > $l::iter().count() - 1 {
-                            0usize
-                        } else {
-                            i + 1
-                        }
-                    })
-                    .unwrap(),
-            )
-            .unwrap()
-            .1
+        <$l as $crate::Cycle>::next($k.current())
     };
 }
 
@@ -48,14 +78,15 @@ enum PluginState {
 }
 
 #[derive(Clone, Eq, PartialEq, Debug, Hash, EnumIter)]
-enum ScrollType {
+pub enum ScrollType {
     MovementSpeed,
     Zoom,
     Sensitivity,
     Lerp,
     CamFwd,
 }
-#[derive(Clone, Eq, PartialEq, Debug, Hash, EnumIter)]
+#[derive(Clone, Eq, PartialEq, Debug, Hash, EnumIter, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum CameraState {
     //Look at player and other targets if set
     LookAt,
@@ -65,6 +96,7 @@ pub enum CameraState {
     TopDown,
     TopDownDirection,
     //Follows behind the player a certain distance
+    #[default]
     FollowBehind,
     //Camera at same position as player, enables to use the mouse to look (WIP)
     Fps,
@@ -72,36 +104,82 @@ pub enum CameraState {
     Free,
 }
 
+#[derive(Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(default))]
 pub struct PlayerKeyMap {
-    pub forward: &'static [KeyCode],
-    pub backward: &'static [KeyCode],
-    pub left: &'static [KeyCode],
-    pub right: &'static [KeyCode],
-    pub up: &'static [KeyCode],
-    pub down: &'static [KeyCode],
-    pub rot_left: &'static [KeyCode],
-    pub rot_right: &'static [KeyCode],
+    pub forward: Vec<KeyCode>,
+    pub backward: Vec<KeyCode>,
+    pub left: Vec<KeyCode>,
+    pub right: Vec<KeyCode>,
+    pub up: Vec<KeyCode>,
+    pub down: Vec<KeyCode>,
+    pub rot_left: Vec<KeyCode>,
+    pub rot_right: Vec<KeyCode>,
+    /// Raises the top speed of the grounded movement
+    pub sprint: Vec<KeyCode>,
+    /// Pitch the nose up and down with the flight movement
+    pub pitch_up: Vec<KeyCode>,
+    pub pitch_down: Vec<KeyCode>,
+    /// Roll with the flight movement
+    pub roll_left: Vec<KeyCode>,
+    pub roll_right: Vec<KeyCode>,
 }
 
+#[derive(Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(default))]
 pub struct PlayerSettings {
-    pub player_asset: &'static str,
+    pub player_asset: String,
+    /// Speed of the player, the top speed of the grounded movement
     pub speed: f32,
     pub map: PlayerKeyMap,
     pub pos: Vec3,
+    /// Moves the player relative to the active camera's view and turns it to face where it moves
     pub cam_fwd: bool,
+    /// How fast the player turns to face where it moves with `cam_fwd`
+    pub face_smoothing: f32,
+    /// Radians per second the player turns at with the rotation keys
+    pub turn_speed: f32,
+    /// Radians per second squared the turning speeds up and slows down with
+    pub turn_acceleration: f32,
+    pub movement: PlayerMovement,
+    pub grounded: GroundedSettings,
+    pub vehicle: VehicleSettings,
+    pub flight: FlightSettings,
+}
+
+/// Gives a player its own controls, for local multiplayer
+///
+/// Players without it are moved with the keys of `PlayerSettings`.
+#[derive(Component, Clone, Default)]
+pub struct PlayerInput {
+    pub map: PlayerKeyMap,
+    /// Gamepad moving the player with its left stick and south/east buttons,
+    /// turning it with its triggers and sprinting with its west button.
+    /// Its right stick pitches and rolls the player with the flight movement
+    pub gamepad: Option<Gamepad>,
+    /// Camera mode of the player's own `PlayerCam`, cycled with the select button of its gamepad.
+    /// `None` leaves the camera where it was spawned, the plugin's own player follows `CamController`
+    pub mode: Option<CameraState>,
 }
 
 impl Default for PlayerKeyMap {
     fn default() -> Self {
         Self {
-            forward: &[KeyCode::Up],
-            backward: &[KeyCode::Down],
-            left: &[KeyCode::Comma],
-            right: &[KeyCode::Period],
-            up: &[KeyCode::RShift],
-            down: &[KeyCode::Minus],
-            rot_left: &[KeyCode::Left],
-            rot_right: &[KeyCode::Right],
+            forward: vec![KeyCode::Up],
+            backward: vec![KeyCode::Down],
+            left: vec![KeyCode::Comma],
+            right: vec![KeyCode::Period],
+            up: vec![KeyCode::RShift],
+            down: vec![KeyCode::Minus],
+            rot_left: vec![KeyCode::Left],
+            rot_right: vec![KeyCode::Right],
+            sprint: vec![KeyCode::RControl],
+            pitch_up: vec![KeyCode::End],
+            pitch_down: vec![KeyCode::Home],
+            roll_left: vec![KeyCode::Delete],
+            roll_right: vec![KeyCode::PageDown],
         }
     }
 }
@@ -109,11 +187,18 @@ impl Default for PlayerKeyMap {
 impl Default for PlayerSettings {
     fn default() -> Self {
         Self {
-            player_asset: "",
-            speed: 12.0,
+            player_asset: String::new(),
+            speed: 4.0,
             map: PlayerKeyMap::default(),
             pos: Default::default(),
             cam_fwd: false,
+            face_smoothing: 10.0,
+            turn_speed: 3.0,
+            turn_acceleration: 20.0,
+            movement: PlayerMovement::default(),
+            grounded: GroundedSettings::default(),
+            vehicle: VehicleSettings::default(),
+            flight: FlightSettings::default(),
         }
     }
 }
@@ -125,21 +210,60 @@ pub struct ConfigCam;
 impl Plugin for ConfigCam {
     fn build(&self, app: &mut App) {
         app.init_resource::<CamLogic>()
+            .init_resource::<CameraFraming>()
+            .init_resource::<zoom::ZoomTarget>()
+            .add_event::<CameraModeChanged>()
+            .add_event::<ScrollTypeChanged>()
+            .add_event::<CameraSettingChanged>()
+            .init_resource::<PhotoMode>()
+            .add_event::<TogglePhotoMode>()
             .add_plugin(NoCameraPlayerPlugin)
             .init_resource::<PlayerSettings>()
             .add_state(PluginState::Enabled)
-            .add_state(CameraState::FollowBehind)
-            .add_state(ScrollType::MovementSpeed)
+            .init_resource::<CamController>()
+            .init_resource::<CamPresets>()
+            .init_resource::<preset::PresetBlend>()
+            .add_event::<SwitchCamPreset>()
             .add_system(toggle_camera_parent.after(MovementUpdate))
+            .add_system(preset::preset_hotkeys.before(MovementUpdate))
+            .add_system(
+                preset::switch_preset
+                    .after(preset::preset_hotkeys)
+                    .before(MovementUpdate),
+            )
+            .add_system(preset::blend_preset.after(toggle_camera_parent))
             .add_system(switch_scroll_type.after(MovementUpdate))
             .add_system(scroll.after(MovementUpdate))
+            .add_system(zoom::zoom.before(MovementUpdate))
+            .add_system(photo::toggle_photo_mode.before(MovementUpdate))
+            .add_system(photo::photo_camera.after(player_look))
             .add_system(cycle_cam_state.after(MovementUpdate))
+            .add_system(multiplayer::cycle_player_modes.before(multiplayer::move_player_cams))
+            .add_system(
+                controller::report_changes
+                    .after(cycle_cam_state)
+                    .after(switch_scroll_type)
+                    .after(preset::switch_preset),
+            )
+            .add_system(events::log_changes.after(MovementUpdate))
             .add_system_set(SystemSet::on_enter(PluginState::Enabled).with_system(setup))
             .add_system_set(
                 SystemSet::on_update(PluginState::Enabled)
                     .with_system(move_player.after(MovementUpdate))
+                    .with_system(multiplayer::move_player_cams.after(move_player))
                     .with_system(move_camera.label(MovementUpdate)),
             );
+
+        #[cfg(feature = "hud")]
+        app.add_plugin(hud::HudPlugin);
+
+        #[cfg(feature = "serde")]
+        app.add_startup_system_to_stage(StartupStage::PreStartup, persist::load_config)
+            // Last, to still save on an exit requested this frame
+            .add_system_to_stage(CoreStage::Last, persist::save_config)
+            .add_asset::<CamProfile>()
+            .init_asset_loader::<CamProfileLoader>()
+            .add_system(profile::apply_cam_profile.before(MovementUpdate));
     }
 }
 
@@ -158,20 +282,21 @@ pub struct CamLogic {
 
 const RESET_FOCUS: [f32; 3] = [0., 0., 0.];
 
-#[allow(unused_must_use)]
 fn cycle_cam_state(
-    mut cam_state: ResMut<State<CameraState>>,
+    mut controller: ResMut<CamController>,
     settings: Res<MovementSettings>,
+    photo: Res<PhotoMode>,
     keyboard_input: Res<Input<KeyCode>>,
 ) {
+    // The photo mode keeps the free camera until it is left
+    if photo.active() {
+        return;
+    }
     if keyboard_input
         .get_just_pressed()
         .any(|m| settings.map.next_cam.iter().any(|nc| m == nc))
     {
-        let result = next_enum!(CameraState, cam_state);
-
-        println!("Camera: {:?}", result);
-        cam_state.set(result);
+        controller.next_mode();
     }
 }
 
@@ -180,6 +305,7 @@ fn setup(
     asset_server: Res<AssetServer>,
     mut _cl: ResMut<CamLogic>,
     settings: Res<PlayerSettings>,
+    zoom_target: Res<zoom::ZoomTarget>,
     mut meshes: ResMut<Assets<Mesh>>,
     mut materials: ResMut<Assets<StandardMaterial>>,
 ) {
@@ -205,7 +331,7 @@ fn setup(
         a.with_children(|_| {})
     } else {
         a.with_children(|cell| {
-            cell.spawn_scene(asset_server.load(settings.player_asset));
+            cell.spawn_scene(asset_server.load(settings.player_asset.as_str()));
         })
     };
 
@@ -229,6 +355,12 @@ fn setup(
             camera: Camera {
                 ..Default::default()
             },
+            perspective_projection: PerspectiveProjection {
+                fov: zoom_target
+                    .fov
+                    .unwrap_or_else(|| PerspectiveProjection::default().fov),
+                ..Default::default()
+            },
             transform: Transform::from_xyz(-2.0, 5.0, 5.0).looking_at(Vec3::ZERO, Vec3::Y),
             ..Default::default()
         })
@@ -236,85 +368,314 @@ fn setup(
 }
 
 // control the cam logic character
+#[allow(clippy::type_complexity, clippy::too_many_arguments)]
 fn move_player(
     keys: Res<Input<KeyCode>>,
-    time: Res<Time>,
+    buttons: Res<Input<GamepadButton>>,
+    axes: Res<Axis<GamepadAxis>>,
+    time: Res<CamTime>,
     settings: Res<PlayerSettings>,
-    mut transforms: Query<(&PlayerMove, &mut Transform)>,
+    ground: Option<Res<PlayerGround>>,
+    photo: Res<PhotoMode>,
+    act_cams: Res<ActiveCamera<Camera3d>>,
+    fly_cams: Query<Entity, MainFlyCam>,
+    cameras: Query<&GlobalTransform>,
+    cl: Res<CamLogic>,
+    player_cams: Query<(&Parent, &GlobalTransform), With<PlayerCam>>,
+    mut move_forwards: Local<HashMap<Entity, Vec3>>,
+    mut commands: Commands,
+    mut transforms: Query<
+        (
+            Entity,
+            &mut Transform,
+            Option<&PlayerInput>,
+            Option<&mut PlayerVelocity>,
+        ),
+        With<PlayerMove>,
+    >,
 ) {
-    for (_player, mut transform) in transforms.iter_mut() {
-        let (_, mut rotation) = transform.rotation.to_axis_angle();
-        let mut velocity = Vec3::ZERO;
-        let local_z = transform.local_z();
-        //Forward should be togglable either xyz or cam direction xyz
-        let forward = if settings.cam_fwd {
-            Vec3::new(local_z.x, 0., local_z.z)
-        } else {
-            -Vec3::new(local_z.x, 0., local_z.z)
+    // Forget the players that are gone, a reused entity starts afresh
+    move_forwards.retain(|entity, _| transforms.contains(*entity));
+    if photo.frozen() {
+        return;
+    }
+    // Forward on the screen, along the ground
+    let ground_forward = |camera: &GlobalTransform| {
+        let forward = Vec3::new(camera.forward().x, 0., camera.forward().z);
+        // Looking straight down the top of the screen points forward
+        let up = Vec3::new(camera.up().x, 0., camera.up().z);
+        forward
+            .try_normalize()
+            .or_else(|| up.try_normalize())
+            .unwrap_or(-Vec3::Z)
+    };
+    let active_forward = act_cams
+        .get()
+        .or_else(|| fly_cams.iter().next())
+        .and_then(|camera| cameras.get(camera).ok())
+        .map(ground_forward)
+        .unwrap_or(-Vec3::Z);
+
+    for (entity, mut transform, input, player_velocity) in transforms.iter_mut() {
+        let map = input.map_or(&settings.map, |input| &input.map);
+        // Other players move relative to their own camera
+        let camera_forward = match player_cams
+            .iter()
+            .find(|(parent, _)| parent.0 == entity && cl.player.entity != Some(entity))
+        {
+            Some((_, camera)) => ground_forward(camera),
+            None => active_forward,
         };
-
-        let right = Vec3::new(local_z.z, 0., -local_z.x);
+        // Players start out without a velocity, it is added once they move
+        let mut added = None;
+        let player_velocity = match player_velocity {
+            Some(player_velocity) => player_velocity.into_inner(),
+            None => added.insert(PlayerVelocity::default()),
+        };
+        // Take out the roll into the last turn, it is added back once the player has turned
+        let bank_axis = Quat::from_rotation_y(yaw(transform.rotation)) * -Vec3::Z;
+        transform.rotation =
+            Quat::from_axis_angle(bank_axis, -player_velocity.bank) * transform.rotation;
+
+        let yaw = yaw(transform.rotation);
+        // Right, up and forward input
+        let mut movement = Vec3::ZERO;
+        // Pitch up, left turning and left roll input
+        let mut spin = Vec3::ZERO;
+        let mut sprint = false;
 
         for key in keys.get_pressed() {
-            if validate_key(settings.map.forward, key) {
-                velocity += forward
+            if validate_key(&map.forward, key) {
+                movement.z += 1.
             }
-            if validate_key(settings.map.backward, key) {
-                velocity -= forward
+            if validate_key(&map.backward, key) {
+                movement.z -= 1.
             }
-            if validate_key(settings.map.left, key) {
-                velocity -= right
+            if validate_key(&map.left, key) {
+                movement.x -= 1.
             }
-            if validate_key(settings.map.right, key) {
-                velocity += right
+            if validate_key(&map.right, key) {
+                movement.x += 1.
             }
-            if validate_key(settings.map.up, key) {
-                velocity += Vec3::Y
+            if validate_key(&map.up, key) {
+                movement.y += 1.
             }
-            if validate_key(settings.map.down, key) {
-                velocity -= Vec3::Y
+            if validate_key(&map.down, key) {
+                movement.y -= 1.
             }
-            if validate_key(settings.map.rot_left, key) {
-                //Wrapping around
-                if rotation > std::f32::consts::FRAC_PI_2 * 4.0 - 0.05 {
-                    rotation = 0.0;
-                }
-                rotation += 0.1
+            if validate_key(&map.rot_left, key) {
+                spin.y += 1.;
             }
-            if validate_key(settings.map.rot_right, key) {
-                //Wrapping around
-                if rotation < 0.05 {
-                    rotation = std::f32::consts::FRAC_PI_2 * 4.0;
-                }
-                rotation -= 0.1
+            if validate_key(&map.rot_right, key) {
+                spin.y -= 1.;
+            }
+            if validate_key(&map.pitch_up, key) {
+                spin.x += 1.;
+            }
+            if validate_key(&map.pitch_down, key) {
+                spin.x -= 1.;
+            }
+            if validate_key(&map.roll_left, key) {
+                spin.z += 1.;
+            }
+            if validate_key(&map.roll_right, key) {
+                spin.z -= 1.;
+            }
+            if validate_key(&map.sprint, key) {
+                sprint = true;
             }
         }
 
-        velocity = velocity.normalize();
+        if let Some(gamepad) = input.and_then(|input| input.gamepad) {
+            let stick = |axis_type| axes.get(GamepadAxis(gamepad, axis_type)).unwrap_or(0.);
+            let pressed = |button_type| buttons.pressed(GamepadButton(gamepad, button_type));
+            movement.x += stick(GamepadAxisType::LeftStickX);
+            movement.z += stick(GamepadAxisType::LeftStickY);
+            spin.x -= stick(GamepadAxisType::RightStickY);
+            spin.z -= stick(GamepadAxisType::RightStickX);
+            if pressed(GamepadButtonType::South) {
+                movement.y += 1.
+            }
+            if pressed(GamepadButtonType::East) {
+                movement.y -= 1.
+            }
+            if pressed(GamepadButtonType::LeftTrigger) {
+                spin.y += 1.;
+            }
+            if pressed(GamepadButtonType::RightTrigger) {
+                spin.y -= 1.;
+            }
+            if pressed(GamepadButtonType::West) {
+                sprint = true;
+            }
+        }
+
+        let vehicle = settings.movement == PlayerMovement::Vehicle;
+        let flight = settings.movement == PlayerMovement::Flight;
+        let forward = if !settings.cam_fwd || vehicle || flight {
+            Quat::from_rotation_y(yaw) * -Vec3::Z
+        } else if movement.x == 0. && movement.z == 0. {
+            move_forwards.remove(&entity);
+            camera_forward
+        } else {
+            // Keep the direction the camera had when the player started moving, so a camera
+            // turning with the player doesn't turn the direction it moves in as well
+            *move_forwards.entry(entity).or_insert(camera_forward)
+        };
+        let right = Vec3::new(-forward.z, 0., forward.x);
+        let horizontal = forward * movement.z + right * movement.x;
+
+        // A half-tilted stick moves the player at half speed
+        let mut velocity = (horizontal + Vec3::Y * movement.y).clamp_length_max(1.);
+
+        let delta_seconds = time.delta_seconds();
+
+        // Radians turned around the world's up axis
+        let mut turned = 0.;
+        if flight {
+            player_velocity.bank = 0.;
+            transform.rotation = player_velocity.turn_freely(
+                &settings.flight,
+                settings.turn_speed,
+                settings.turn_acceleration,
+                spin,
+                transform.rotation,
+                delta_seconds,
+            );
+            velocity = (transform.rotation * Vec3::new(movement.x, movement.y, -movement.z))
+                .clamp_length_max(1.);
+        } else {
+            let target = if vehicle {
+                let forward_speed = player_velocity.velocity.dot(forward);
+                let steer = spin.y - movement.x;
+                settings
+                    .vehicle
+                    .steer_rate(steer, forward_speed, settings.speed)
+            } else {
+                spin.y.clamp(-1., 1.) * settings.turn_speed
+            };
+            let max_change = settings.turn_acceleration * delta_seconds;
+            player_velocity.angular_velocity +=
+                (target - player_velocity.angular_velocity).clamp(-max_change, max_change);
+            turned = player_velocity.angular_velocity * delta_seconds;
+
+            if settings.cam_fwd && !vehicle && (velocity.x != 0. || velocity.z != 0.) {
+                let facing = (-velocity.x).atan2(-velocity.z);
+                let difference = (facing - yaw - turned + PI).rem_euclid(TAU) - PI;
+                let t = 1. - (-settings.face_smoothing * delta_seconds).exp();
+                turned += difference * t;
+            }
 
-        transform.rotation = Quat::from_rotation_y(rotation);
+            // Turning around the world's up axis keeps the pitch and roll of the model
+            transform.rotation = Quat::from_rotation_y(turned) * transform.rotation;
+            player_velocity.bank = if vehicle {
+                settings.vehicle.bank(player_velocity.angular_velocity)
+            } else {
+                0.
+            };
+            let bank_axis = Quat::from_rotation_y(yaw + turned) * -Vec3::Z;
+            transform.rotation =
+                Quat::from_axis_angle(bank_axis, player_velocity.bank) * transform.rotation;
+        }
 
-        if !velocity.is_nan() {
-            transform.translation += velocity * time.delta_seconds() * 4.0;
+        match settings.movement {
+            PlayerMovement::Fly | PlayerMovement::Flight => {
+                let from = transform.translation;
+                let mut translation = from + velocity * delta_seconds * settings.speed;
+                if let Some(ground) = &ground {
+                    translation =
+                        fly_over_ground(ground, settings.grounded.height, from, translation);
+                }
+                transform.translation = translation;
+                player_velocity.velocity = velocity * settings.speed;
+                player_velocity.grounded = false;
+            }
+            PlayerMovement::Grounded => {
+                transform.translation = player_velocity.step(
+                    &settings.grounded,
+                    ground.as_deref(),
+                    settings.speed,
+                    // Jumping doesn't take away from the speed along the ground
+                    horizontal,
+                    movement.y > 0.,
+                    sprint,
+                    transform.translation,
+                    delta_seconds,
+                );
+            }
+            PlayerMovement::Vehicle => {
+                transform.translation = player_velocity.drive(
+                    &settings.vehicle,
+                    ground.as_deref(),
+                    settings.speed,
+                    movement.z,
+                    yaw + turned,
+                    transform.translation,
+                    delta_seconds,
+                    time.seconds_since_startup() as f32,
+                );
+            }
+        }
+        if let Some(added) = added {
+            commands.entity(entity).insert(added);
         }
     }
 }
 
+/// Keeps a flying player above the ground, and from flying into slopes too steep to walk up
+fn fly_over_ground(ground: &PlayerGround, height: f32, from: Vec3, mut to: Vec3) -> Vec3 {
+    let (from_xz, to_xz) = (Vec2::new(from.x, from.z), Vec2::new(to.x, to.z));
+    let on_ground = ground
+        .ground
+        .height(from_xz)
+        .is_some_and(|floor| from.y <= floor + height + 1e-4);
+    if on_ground && !ground.walkable(from_xz, to_xz) {
+        to.x = from.x;
+        to.z = from.z;
+    }
+    if let Some(floor) = ground.ground.height(Vec2::new(to.x, to.z)) {
+        to.y = to.y.max(floor + height);
+    }
+    to
+}
+
+/// Rotation around the world's up axis, exact for models with only a pitch or roll on top of it
+fn yaw(rotation: Quat) -> f32 {
+    2. * rotation.y.atan2(rotation.w)
+}
+
 // change the focus of the camera
-#[allow(clippy::type_complexity)]
+#[allow(clippy::type_complexity, clippy::too_many_arguments)]
 fn move_camera(
-    time: Res<Time>,
-    state: Res<State<CameraState>>,
+    time: Res<CamTime>,
+    controller: Res<CamController>,
     mut cl: ResMut<CamLogic>,
     mut settings: ResMut<MovementSettings>,
-    mut transforms: ParamSet<(Query<&mut Transform, With<Camera3d>>, Query<&Transform>)>,
+    p_settings: Res<PlayerSettings>,
+    framing: Res<CameraFraming>,
+    confiner: Option<Res<CameraConfiner>>,
+    fly_cams: Query<(Entity, &PerspectiveProjection), MainFlyCam>,
+    mut transforms: ParamSet<(
+        Query<(Entity, &mut Transform, Option<&Parent>), (With<Camera3d>, Without<CamWindow>)>,
+        Query<&Transform>,
+    )>,
+    player_cams: Query<(), With<PlayerCam>>,
 ) {
     let mut delta_trans = Transform::identity();
     settings.disable_look = true;
     settings.disable_move = false;
     settings.locked_to_player = false;
 
-    match *state.current() {
+    // A flying player can turn upside down, the camera then rolls with it
+    let up = match (p_settings.movement, cl.player.entity) {
+        (PlayerMovement::Flight, Some(player_entity)) => transforms
+            .p1()
+            .get(player_entity)
+            .map_or(Vec3::Y, |player_transform| player_transform.up()),
+        _ => Vec3::Y,
+    };
+
+    match *controller.mode() {
         CameraState::Free => {
             settings.disable_look = false;
             return;
@@ -344,21 +705,18 @@ fn move_camera(
         _ => {
             if let Some(player_entity) = cl.player.entity {
                 if let Ok(player_transform) = transforms.p1().get(player_entity) {
-                    match *state.current() {
+                    match *controller.mode() {
                         CameraState::Fps => {
                             delta_trans.translation = player_transform.translation;
                             settings.disable_move = true;
 
                             settings.disable_look = false;
                             delta_trans.rotation = player_transform.rotation;
-                            delta_trans.translation += Vec3::new(/*-4.*/ 0., 1., 0.);
+                            delta_trans.translation += up;
                         }
                         CameraState::TopDown => {
-                            delta_trans.translation = player_transform.translation;
                             settings.disable_move = true;
 
-                            delta_trans.translation +=
-                                Vec3::new(/*-4.*/ 0., settings.dist, 0.);
                             delta_trans.rotation =
                                 Quat::from_rotation_x(-std::f32::consts::FRAC_PI_2)
                         }
@@ -376,7 +734,8 @@ fn move_camera(
 
                             settings.locked_to_player = true;
 
-                            delta_trans.translation += Vec3::new(/*-4.*/ 0., 1., 4.);
+                            delta_trans.translation +=
+                                Vec3::new(/*-4.*/ 0., 0.25, 1.) * settings.follow_dist;
                         }
                         _ => {}
                     }
@@ -389,24 +748,47 @@ fn move_camera(
         }
     }
 
-    const SPEED: f32 = 2.0;
+    // move the camera's actual focus towards where it should be looking, using the framing
+    // of the current mode to decide how much of the difference to catch up on this frame
+    if let Ok((fly_cam, projection)) = fly_cams.get_single() {
+        if let Ok(cam_transform) = transforms.p1().get(fly_cam) {
+            cl.camera_is_focus = framing.get(controller.mode()).frame(
+                cam_transform,
+                projection,
+                cl.camera_is_focus,
+                cl.camera_should_focus,
+                time.delta_seconds(),
+            );
+        }
+    }
 
-    // calculate the camera motion based on the difference between where the camera is looking
-    // and where it should be looking; the greater the distance, the faster the motion;
-    // smooth out the camera movement using the frame time
-    let mut camera_motion = cl.camera_should_focus - cl.camera_is_focus;
-    if camera_motion.length() > 0.2 {
-        camera_motion *= SPEED * time.delta_seconds();
-        // set the new camera's actual focus
-        cl.camera_is_focus += camera_motion;
+    if *controller.mode() == CameraState::TopDown {
+        delta_trans.translation =
+            cl.camera_is_focus + Vec3::new(/*-4.*/ 0., settings.dist, 0.);
     }
     // look at that new camera's actual focus
-    for mut transform in transforms.p0().iter_mut() {
-        if delta_trans.translation != Vec3::ZERO {
-            *transform = delta_trans
+    let fly_cam = fly_cams.get_single().ok().map(|(entity, _)| entity);
+    for (entity, mut transform, parent) in transforms.p0().iter_mut() {
+        // The cameras of other players keep the transform they were spawned with
+        if player_cams.contains(entity) && parent.map(|parent| parent.0) != cl.player.entity {
+            continue;
+        }
+        let mut new_transform = if delta_trans.translation != Vec3::ZERO {
+            delta_trans
         } else {
-            *transform = transform.looking_at(cl.camera_is_focus, Vec3::Y)
+            transform.looking_at(cl.camera_is_focus, up)
+        };
+        // only the fly cam lives in world space, the player cam moves with the player,
+        // and only the top-down view roams the level, the other modes stay by the player
+        let confined = fly_cam == Some(entity) && *controller.mode() == CameraState::TopDown;
+        if let (Some(confiner), true) = (&confiner, confined) {
+            new_transform.translation = confiner.confine(
+                transform.translation,
+                new_transform.translation,
+                time.delta_seconds(),
+            );
         }
+        *transform = new_transform;
     }
 }
 
@@ -414,132 +796,218 @@ fn move_camera(
 fn toggle_camera_parent(
     mut act_cams: ResMut<ActiveCamera<Camera3d>>,
     mut settings: ResMut<MovementSettings>,
-    mut query: ParamSet<(Query<(&FlyCam, Entity)>, Query<(&PlayerCam, Entity)>)>,
+    cl: Res<CamLogic>,
+    mut query: ParamSet<(
+        Query<(&FlyCam, Entity), Without<CamWindow>>,
+        Query<(&PlayerCam, Entity, &Parent)>,
+    )>,
 ) {
     if settings.locked_to_player && !settings.ltp {
+        // Other players may have a player cam of their own
         let p1 = query.p1();
-        let (_, b) = p1.single();
-        act_cams.set(b);
+        if let Some((_, b, _)) = p1
+            .iter()
+            .find(|(_, _, parent)| Some(parent.0) == cl.player.entity)
+        {
+            act_cams.set(b);
+        }
 
         settings.ltp = true;
     } else if !settings.locked_to_player && settings.ltp {
         let pr = query.p0();
-        let (_, b) = pr.single();
-        act_cams.set(b);
+        if let Some((_, b)) = pr.iter().next() {
+            act_cams.set(b);
+        }
         settings.ltp = false;
     }
 }
 
-// Listens for Z key being pressed and toggles between the scroll-type states
-#[allow(unused_must_use)]
+// Listens for the next setting key being pressed and toggles between the scroll-types
 fn switch_scroll_type(
-    mut scroll_type: ResMut<State<ScrollType>>,
+    mut controller: ResMut<CamController>,
+    settings: Res<MovementSettings>,
     keyboard_input: Res<Input<KeyCode>>,
 ) {
-    if keyboard_input.just_pressed(KeyCode::E) {
-        let result = next_enum!(ScrollType, scroll_type);
-
-        println!("{:?}", result);
-        scroll_type.set(result);
+    if keyboard_input
+        .get_just_pressed()
+        .any(|m| validate_key(&settings.map.next_setting, m))
+    {
+        controller.next_scroll_type();
     }
 }
 
-// Depending on the state, the mouse-scroll changes either the movement speed or the field-of-view of the camera
+// Depending on the state, the mouse-scroll changes either the movement speed or the zoom of the camera
+#[allow(clippy::too_many_arguments)]
 fn scroll(
     mut settings: ResMut<MovementSettings>,
     mut p_settings: ResMut<PlayerSettings>,
-    scroll_type: Res<State<ScrollType>>,
+    controller: Res<CamController>,
+    mut zoom_target: ResMut<zoom::ZoomTarget>,
     mut mouse_wheel_events: EventReader<MouseWheel>,
-    windows: Res<Windows>,
-    mut query: Query<(&FlyCam, &mut Camera, &mut PerspectiveProjection)>,
+    mut events: EventWriter<CameraSettingChanged>,
+    mut cam_fwd_lines: Local<f32>,
+    query: Query<&PerspectiveProjection, MainFlyCam>,
 ) {
     for event in mouse_wheel_events.iter() {
-        match *scroll_type.current() {
+        let lines = settings.scroll.lines(event);
+        let (old, new) = match *controller.scroll_type() {
             ScrollType::MovementSpeed => {
-                settings.speed = (settings.speed + event.y * 0.1).abs();
-                println!("Speed: {:?}", settings.speed);
+                let old = settings.speed;
+                settings.speed = settings.scroll.speed.apply(old, lines);
+                (old, settings.speed)
             }
             ScrollType::Sensitivity => {
-                settings.sensitivity = (settings.sensitivity + event.y * 0.000001).abs();
-                println!("Sensitivity: {:?}", settings.sensitivity);
+                let old = settings.sensitivity;
+                settings.sensitivity = settings.scroll.sensitivity.apply(old, lines);
+                (old, settings.sensitivity)
             }
-            ScrollType::Zoom => {
-                for (_camera, mut camera, mut project) in query.iter_mut() {
-                    project.fov = (project.fov - event.y * 0.01).abs();
-                    let prim = windows.get_primary().unwrap();
-
-                    //Calculate projection with new fov
-                    project.update(prim.width(), prim.height());
-
-                    //Update camera with the new fov
-                    camera.projection_matrix = project.get_projection_matrix();
-                    camera.depth_calculation = project.depth_calculation();
-
-                    println!("FOV: {:?}", project.fov);
+            ScrollType::Zoom => match *controller.mode() {
+                CameraState::TopDown | CameraState::TopDownDirection => {
+                    let old = zoom_target.dist.unwrap_or(settings.dist);
+                    let dist = settings.zoom.zoom_dist(old, lines);
+                    zoom_target.dist = Some(dist);
+                    (old, dist)
                 }
-            }
+                CameraState::FollowBehind => {
+                    let old = zoom_target.follow_dist.unwrap_or(settings.follow_dist);
+                    let dist = settings.zoom.zoom_dist(old, lines);
+                    zoom_target.follow_dist = Some(dist);
+                    (old, dist)
+                }
+                _ => {
+                    let project = match query.get_single() {
+                        Ok(project) => project,
+                        Err(_) => continue,
+                    };
+                    let old = zoom_target.fov.unwrap_or(project.fov);
+                    let fov = settings.zoom.zoom_fov(old, lines);
+                    zoom_target.fov = Some(fov);
+                    (old, fov)
+                }
+            },
             ScrollType::Lerp => {
-                settings.lerp = (settings.lerp + event.y * 0.01).abs();
-                println!("Lerp: {:?}", settings.lerp);
+                let old = settings.lerp;
+                settings.lerp = settings.scroll.lerp.apply(old, lines);
+                (old, settings.lerp)
             }
             ScrollType::CamFwd => {
-                if event.y > 0.01 {
+                let old = p_settings.cam_fwd;
+                // Toggle once per line scrolled up, so trackpads don't toggle on every event
+                *cam_fwd_lines = (*cam_fwd_lines + lines).max(0.);
+                if *cam_fwd_lines >= 1. {
+                    *cam_fwd_lines = 0.;
                     p_settings.cam_fwd = !p_settings.cam_fwd;
                 }
-                println!("CamFwd: {:?}", p_settings.cam_fwd);
+                (old as u8 as f32, p_settings.cam_fwd as u8 as f32)
             }
+        };
+
+        if old != new {
+            events.send(CameraSettingChanged {
+                setting: controller.scroll_type().clone(),
+                old,
+                new,
+            });
         }
     }
 }
 
 //bevy_flycam by sburris0 - https://github.com/sburris0/bevy_flycam
 
-/// Keeps track of mouse motion events, pitch, and yaw
+/// Keeps track of mouse motion events, and the pitch and yaw of every fly cam
 #[derive(Default)]
 struct InputState {
     reader_motion: ManualEventReader<MouseMotion>,
-    pitch: f32,
-    yaw: f32,
+    looks: HashMap<Entity, (f32, f32)>,
 }
 
+#[derive(Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(default))]
 pub struct CamKeyMap {
-    pub forward: &'static [KeyCode],
-    pub backward: &'static [KeyCode],
-    pub left: &'static [KeyCode],
-    pub right: &'static [KeyCode],
-    pub up: &'static [KeyCode],
-    pub down: &'static [KeyCode],
-    pub next_cam: &'static [KeyCode],
-    pub next_setting: &'static [KeyCode],
+    pub forward: Vec<KeyCode>,
+    pub backward: Vec<KeyCode>,
+    pub left: Vec<KeyCode>,
+    pub right: Vec<KeyCode>,
+    pub up: Vec<KeyCode>,
+    pub down: Vec<KeyCode>,
+    pub next_cam: Vec<KeyCode>,
+    pub next_setting: Vec<KeyCode>,
+    /// Roll the free camera when it is `unconstrained`
+    pub roll_left: Vec<KeyCode>,
+    pub roll_right: Vec<KeyCode>,
+    /// Speed the free camera up by `MovementSettings::boost` while held
+    pub boost: Vec<KeyCode>,
+    /// Slow the free camera down by `MovementSettings::slow` while held
+    pub slow: Vec<KeyCode>,
+    /// Enter and leave the photo mode
+    pub photo_mode: Vec<KeyCode>,
+    /// Hide and show the UI in the photo mode
+    pub toggle_ui: Vec<KeyCode>,
 }
 
 impl Default for CamKeyMap {
     fn default() -> Self {
         Self {
-            forward: &[KeyCode::W],
-            backward: &[KeyCode::S],
-            left: &[KeyCode::A],
-            right: &[KeyCode::D],
-            up: &[KeyCode::Space],
-            down: &[KeyCode::LShift],
-            next_cam: &[KeyCode::C],
-            next_setting: &[KeyCode::E],
+            forward: vec![KeyCode::W],
+            backward: vec![KeyCode::S],
+            left: vec![KeyCode::A],
+            right: vec![KeyCode::D],
+            up: vec![KeyCode::Space],
+            down: vec![KeyCode::LShift],
+            next_cam: vec![KeyCode::C],
+            next_setting: vec![KeyCode::E],
+            roll_left: vec![KeyCode::Q],
+            roll_right: vec![KeyCode::R],
+            boost: vec![KeyCode::LAlt],
+            slow: vec![KeyCode::LControl],
+            photo_mode: vec![KeyCode::P],
+            toggle_ui: vec![KeyCode::H],
         }
     }
 }
 
 /// Mouse sensitivity and movement speed
+#[derive(Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(default))]
 pub struct MovementSettings {
     pub sensitivity: f32,
     pub speed: f32,
     pub dist: f32,
+    pub follow_dist: f32,
+    pub zoom: ZoomSettings,
+    pub scroll: ScrollSettings,
+    pub photo: PhotoSettings,
     pub map: CamKeyMap,
     //pub force_cam: &'static[CameraState],
+    #[cfg_attr(feature = "serde", serde(skip))]
     pub disable_move: bool,
+    #[cfg_attr(feature = "serde", serde(skip))]
     pub disable_look: bool,
+    #[cfg_attr(feature = "serde", serde(skip))]
     pub locked_to_player: bool,
     pub lerp: f32,
-
+    /// Lets the free camera loop and roll, looking around its own axes instead of the world's up
+    pub unconstrained: bool,
+    /// Radians per second the free camera rolls at when it is `unconstrained`
+    pub roll_speed: f32,
+    /// Moves the free camera where it looks, including up and down, instead of along the ground
+    pub look_flight: bool,
+    /// Moves the free camera up and down along its own up instead of the world's
+    pub camera_up: bool,
+    /// Multiplier of the free camera's speed while a boost key is held
+    pub boost: f32,
+    /// Multiplier of the free camera's speed while a slow key is held
+    pub slow: f32,
+    /// Seconds the free camera takes to get most of the way up to speed, 0 to start instantly
+    pub acceleration: f32,
+    /// Seconds the free camera glides for before mostly stopping, 0 to stop instantly
+    pub deceleration: f32,
+    /// Log camera mode and setting changes, see `CameraModeChanged` and the other events to react to them
+    pub log: bool,
+
+    #[cfg_attr(feature = "serde", serde(skip))]
     pub ltp: bool,
 }
 
@@ -549,33 +1017,37 @@ impl Default for MovementSettings {
             sensitivity: 0.00012,
             speed: 12.,
             dist: 10.,
+            follow_dist: 4.,
+            zoom: ZoomSettings::default(),
+            scroll: ScrollSettings::default(),
+            photo: PhotoSettings::default(),
             map: CamKeyMap::default(),
             disable_move: false,
             disable_look: false,
             locked_to_player: false,
             lerp: 0.5,
+            unconstrained: false,
+            roll_speed: 1.5,
+            look_flight: false,
+            camera_up: false,
+            boost: 4.,
+            slow: 0.25,
+            acceleration: 0.,
+            deceleration: 0.,
+            log: true,
             ltp: false,
         }
     }
 }
 
 /// Used in queries when you want flycams and not other cameras
-
 #[derive(Component)]
-struct FlyCam;
+pub struct FlyCam;
+/// Filter for the plugin's fly cam, leaving out free cameras with their own `CamWindow`
+pub(crate) type MainFlyCam = (With<FlyCam>, Without<CamWindow>);
+/// Used in queries when you want the camera attached to the player
 #[derive(Component)]
-struct PlayerCam;
-
-/// Grabs/ungrabs mouse cursor
-fn toggle_grab_cursor(window: &mut Window) {
-    window.set_cursor_lock_mode(!window.cursor_locked());
-    window.set_cursor_visibility(!window.cursor_visible());
-}
-
-/// Grabs the cursor when game first starts
-fn initial_grab_cursor(mut windows: ResMut<Windows>) {
-    toggle_grab_cursor(windows.get_primary_mut().unwrap());
-}
+pub struct PlayerCam;
 
 /// Spawns the `Camera3dBundle` to be controlled
 fn setup_player(mut commands: Commands) {
@@ -588,55 +1060,103 @@ fn setup_player(mut commands: Commands) {
 }
 
 /// Handles keyboard input and movement
+#[allow(clippy::too_many_arguments)]
 fn player_move(
     keys: Res<Input<KeyCode>>,
-    time: Res<Time>,
-    windows: Res<Windows>,
+    time: Res<CamTime>,
+    windows: Option<Res<Windows>>,
+    cam_window: Option<Res<CamWindow>>,
     settings: Res<MovementSettings>,
-    mut query: Query<(&FlyCam, &mut Transform)>,
+    confiner: Option<Res<CameraConfiner>>,
+    mut velocities: Local<HashMap<Entity, Vec3>>,
+    mut query: Query<(Entity, &mut Transform, Option<&CamWindow>), With<FlyCam>>,
 ) {
-    if settings.disable_move {
-        return;
-    }
-    let window = windows.get_primary().unwrap();
-    for (_camera, mut transform) in query.iter_mut() {
-        let mut velocity = Vec3::ZERO;
+    // Forget the fly cams that are gone, a reused entity starts at rest
+    velocities.retain(|entity, _| query.contains(*entity));
+    for (entity, mut transform, own_window) in query.iter_mut() {
+        // A fly cam with its own window is a free camera, whatever the camera mode
+        if settings.disable_move && own_window.is_none() {
+            velocities.remove(&entity);
+            continue;
+        }
+        let (cursor_locked, _) = cursor_state(&windows, window_id(own_window, &cam_window));
+        let mut direction = Vec3::ZERO;
+        let mut speed = settings.speed;
         let local_z = transform.local_z();
-        let forward = -Vec3::new(local_z.x, 0., local_z.z);
-        let right = Vec3::new(local_z.z, 0., -local_z.x);
+        let (forward, right) = if settings.look_flight {
+            (-local_z, transform.local_x())
+        } else {
+            (
+                -Vec3::new(local_z.x, 0., local_z.z),
+                Vec3::new(local_z.z, 0., -local_z.x),
+            )
+        };
+        let up = if settings.camera_up {
+            transform.local_y()
+        } else {
+            Vec3::Y
+        };
 
         for key in keys.get_pressed() {
-            if window.cursor_locked() {
-                if validate_key(settings.map.forward, key) {
-                    velocity += forward
+            if cursor_locked {
+                if validate_key(&settings.map.forward, key) {
+                    direction += forward
+                }
+                if validate_key(&settings.map.backward, key) {
+                    direction -= forward
                 }
-                if validate_key(settings.map.backward, key) {
-                    velocity -= forward
+                if validate_key(&settings.map.left, key) {
+                    direction -= right
                 }
-                if validate_key(settings.map.left, key) {
-                    velocity -= right
+                if validate_key(&settings.map.right, key) {
+                    direction += right
                 }
-                if validate_key(settings.map.right, key) {
-                    velocity += right
+                if validate_key(&settings.map.up, key) {
+                    direction += up
                 }
-                if validate_key(settings.map.up, key) {
-                    velocity += Vec3::Y
+                if validate_key(&settings.map.down, key) {
+                    direction -= up
                 }
-                if validate_key(settings.map.down, key) {
-                    velocity -= Vec3::Y
+                if validate_key(&settings.map.boost, key) {
+                    speed *= settings.boost
+                }
+                if validate_key(&settings.map.slow, key) {
+                    speed *= settings.slow
                 }
             }
         }
 
-        velocity = velocity.normalize();
+        let target = direction.normalize_or_zero() * speed;
+        let velocity = velocities.entry(entity).or_default();
+        let time_constant = if target == Vec3::ZERO {
+            settings.deceleration
+        } else {
+            settings.acceleration
+        };
+        *velocity = if time_constant > 0. {
+            *velocity + (target - *velocity) * (1. - (-time.delta_seconds() / time_constant).exp())
+        } else {
+            target
+        };
+        // Come to a full stop at the end of a glide
+        if target == Vec3::ZERO && velocity.length_squared() < 1e-6 {
+            *velocity = Vec3::ZERO;
+        }
 
-        if !velocity.is_nan() {
-            transform.translation += velocity * time.delta_seconds() * settings.speed
+        if *velocity != Vec3::ZERO {
+            let translation = transform.translation + *velocity * time.delta_seconds();
+            // A fly cam with its own window is an editor camera, free to leave the playable area
+            transform.translation = match (&confiner, own_window) {
+                (Some(confiner), None) => {
+                    confiner.confine(transform.translation, translation, time.delta_seconds())
+                }
+                _ => translation,
+            };
         }
     }
 }
 
-pub fn validate_key<T>(codes: &'static [T], key: &T) -> bool
+pub fn validate_key<T>(codes: &[T], key: &T) -> bool
 where
     T: PartialEq<T>,
 {
@@ -644,40 +1164,80 @@ where
 }
 
 /// Handles looking around if cursor is locked
+#[allow(clippy::too_many_arguments)]
 fn player_look(
+    keys: Res<Input<KeyCode>>,
+    time: Res<CamTime>,
     settings: Res<MovementSettings>,
-    windows: Res<Windows>,
+    windows: Option<Res<Windows>>,
+    cam_window: Option<Res<CamWindow>>,
     mut state: ResMut<InputState>,
     motion: Res<Events<MouseMotion>>,
-    mut query: Query<(&FlyCam, &mut Transform)>,
+    mut query: Query<(Entity, &mut Transform, Option<&CamWindow>), With<FlyCam>>,
 ) {
-    if settings.disable_look {
+    let state = state.as_mut();
+    // Forget the fly cams that are gone, a reused entity starts looking ahead
+    state.looks.retain(|entity, _| query.contains(*entity));
+    let deltas: Vec<Vec2> = state
+        .reader_motion
+        .iter(&motion)
+        .map(|ev| ev.delta)
+        .collect();
+    let mut roll = 0.;
+    if settings.unconstrained {
+        for key in keys.get_pressed() {
+            if validate_key(&settings.map.roll_left, key) {
+                roll += 1.;
+            }
+            if validate_key(&settings.map.roll_right, key) {
+                roll -= 1.;
+            }
+        }
+    }
+    if deltas.is_empty() && roll == 0. {
         return;
     }
-    let window = windows.get_primary().unwrap();
-    let mut state_delta = state.as_mut();
-    for (_camera, mut transform) in query.iter_mut() {
-        for ev in state_delta.reader_motion.iter(&motion) {
-            if window.cursor_locked() {
-                state_delta.pitch -=
-                    (settings.sensitivity * ev.delta.y * window.height()).to_radians();
-                state_delta.yaw -=
-                    (settings.sensitivity * ev.delta.x * window.width()).to_radians();
-            }
-
-            state_delta.pitch = state_delta.pitch.clamp(-1.54, 1.54);
-
-            // Order is important to prevent unintended roll
-            transform.rotation = Quat::from_axis_angle(Vec3::Y, state_delta.yaw)
-                * Quat::from_axis_angle(Vec3::X, state_delta.pitch);
+    for (entity, mut transform, own_window) in query.iter_mut() {
+        // A fly cam with its own window is a free camera, whatever the camera mode
+        if settings.disable_look && own_window.is_none() {
+            continue;
         }
-    }
-}
+        let (cursor_locked, size) = cursor_state(&windows, window_id(own_window, &cam_window));
+        let (pitch, yaw) = state.looks.entry(entity).or_default();
 
-fn cursor_grab(keys: Res<Input<KeyCode>>, mut windows: ResMut<Windows>) {
-    let window = windows.get_primary_mut().unwrap();
-    if keys.just_pressed(KeyCode::Escape) {
-        toggle_grab_cursor(window);
+        if settings.unconstrained {
+            if !cursor_locked {
+                continue;
+            }
+            // Turn around the camera's own axes, so it can loop over and roll
+            for delta in deltas.iter() {
+                let pitch = -(settings.sensitivity * delta.y * size.y).to_radians();
+                let yaw = -(settings.sensitivity * delta.x * size.x).to_radians();
+                transform.rotation =
+                    transform.rotation * Quat::from_rotation_y(yaw) * Quat::from_rotation_x(pitch);
+            }
+            let roll = roll * settings.roll_speed * time.delta_seconds();
+            transform.rotation = (transform.rotation * Quat::from_rotation_z(roll)).normalize();
+
+            // Carry on from the same direction when switching back
+            let (new_yaw, new_pitch, _) = transform.rotation.to_euler(EulerRot::YXZ);
+            *yaw = new_yaw;
+            *pitch = new_pitch.clamp(-1.54, 1.54);
+            continue;
+        }
+
+        for delta in deltas.iter() {
+            if cursor_locked {
+                *pitch -= (settings.sensitivity * delta.y * size.y).to_radians();
+                *yaw -= (settings.sensitivity * delta.x * size.x).to_radians();
+            }
+
+            *pitch = pitch.clamp(-1.54, 1.54);
+        }
+
+        // Order is important to prevent unintended roll
+        transform.rotation =
+            Quat::from_axis_angle(Vec3::Y, *yaw) * Quat::from_axis_angle(Vec3::X, *pitch);
     }
 }
 
@@ -687,11 +1247,16 @@ impl Plugin for PlayerPlugin {
     fn build(&self, app: &mut App) {
         app.init_resource::<InputState>()
             .init_resource::<MovementSettings>()
+            .init_resource::<CamTime>()
+            .add_system_to_stage(
+                CoreStage::First,
+                time::update_cam_time.after(CoreSystem::Time),
+            )
             .add_startup_system(setup_player)
-            .add_startup_system(initial_grab_cursor)
+            .add_system(window::initial_grab_cursor.after(MovementUpdate))
             .add_system(player_move.before(MovementUpdate))
             .add_system(player_look.after(MovementUpdate))
-            .add_system(cursor_grab.after(MovementUpdate));
+            .add_system(window::cursor_grab.after(window::initial_grab_cursor));
     }
 }
 
@@ -701,9 +1266,14 @@ impl Plugin for NoCameraPlayerPlugin {
     fn build(&self, app: &mut App) {
         app.init_resource::<InputState>()
             .init_resource::<MovementSettings>()
-            .add_startup_system(initial_grab_cursor)
+            .init_resource::<CamTime>()
+            .add_system_to_stage(
+                CoreStage::First,
+                time::update_cam_time.after(CoreSystem::Time),
+            )
+            .add_system(window::initial_grab_cursor.after(MovementUpdate))
             .add_system(player_move.before(MovementUpdate))
             .add_system(player_look.after(MovementUpdate))
-            .add_system(cursor_grab.after(MovementUpdate));
+            .add_system(window::cursor_grab.after(window::initial_grab_cursor));
     }
 }
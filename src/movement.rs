@@ -0,0 +1,332 @@
+use std::f32::consts::TAU;
+
+use bevy::prelude::*;
+
+use crate::PlayerGround;
+
+/// How the player is moved
+#[derive(Clone, Copy, Eq, PartialEq, Debug, Hash, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum PlayerMovement {
+    /// Moves at a constant speed in any direction, including up and down
+    #[default]
+    Fly,
+    /// Accelerates along the ground, falls with gravity and jumps with the up keys
+    Grounded,
+    /// Drives like a hovering vehicle with throttle, brakes and steering
+    Vehicle,
+    /// Flies like a plane or spaceship, pitching, turning and rolling around its own axes
+    Flight,
+}
+
+/// Tuning of the `Grounded` player movement, its top speed is `PlayerSettings::speed`
+#[derive(Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(default))]
+pub struct GroundedSettings {
+    /// Units per second squared gained while a movement key is held
+    pub acceleration: f32,
+    /// Units per second squared lost when no movement key is held
+    pub deceleration: f32,
+    pub gravity: f32,
+    /// Upwards speed the player jumps with
+    pub jump_speed: f32,
+    /// Multiplier of the top speed while a sprint key is held
+    pub sprint: f32,
+    /// Height of the player's origin above the ground when standing on it
+    pub height: f32,
+}
+
+impl Default for GroundedSettings {
+    fn default() -> Self {
+        Self {
+            acceleration: 30.,
+            deceleration: 40.,
+            gravity: 20.,
+            jump_speed: 7.,
+            sprint: 1.8,
+            height: 0.5,
+        }
+    }
+}
+
+/// Tuning of the `Vehicle` player movement, its top speed is `PlayerSettings::speed`
+///
+/// The forward keys are the throttle, the backward keys brake and reverse, and the left, right
+/// and rotation keys steer.
+#[derive(Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(default))]
+pub struct VehicleSettings {
+    /// Units per second squared gained with the throttle
+    pub acceleration: f32,
+    /// Units per second squared lost when braking
+    pub brake: f32,
+    /// Units per second squared lost without throttle or brakes
+    pub drag: f32,
+    /// Top reversing speed as a fraction of the top speed
+    pub reverse: f32,
+    /// Radians per second the vehicle turns at top speed, it doesn't turn standing still
+    pub steering: f32,
+    /// How fast sideways sliding stops, lower values drift more
+    pub grip: f32,
+    /// Radians the vehicle rolls into a turn when steering fully
+    pub bank: f32,
+    /// Height of the player's origin above the ground
+    pub hover_height: f32,
+    /// Units the vehicle bobs up and down while hovering
+    pub bob_height: f32,
+    /// Bobs per second
+    pub bob_frequency: f32,
+}
+
+impl Default for VehicleSettings {
+    fn default() -> Self {
+        Self {
+            acceleration: 10.,
+            brake: 20.,
+            drag: 3.,
+            reverse: 0.4,
+            steering: 2.5,
+            grip: 6.,
+            bank: 0.3,
+            hover_height: 0.5,
+            bob_height: 0.05,
+            bob_frequency: 0.8,
+        }
+    }
+}
+
+impl VehicleSettings {
+    /// Radians per second to turn at with `steer` in -1 to 1, turned the other way when reversing
+    pub(crate) fn steer_rate(&self, steer: f32, forward_speed: f32, max_speed: f32) -> f32 {
+        if max_speed <= 0. {
+            return 0.;
+        }
+        steer.clamp(-1., 1.) * self.steering * (forward_speed / max_speed).clamp(-1., 1.)
+    }
+
+    /// Roll into a turn at `angular_velocity`, around the vehicle's forward axis
+    pub(crate) fn bank(&self, angular_velocity: f32) -> f32 {
+        if self.steering <= 0. {
+            return 0.;
+        }
+        -self.bank * (angular_velocity / self.steering).clamp(-1., 1.)
+    }
+}
+
+/// Tuning of the `Flight` player movement, its speed is `PlayerSettings::speed`
+///
+/// The player moves along and turns around its own axes, its turning speeds up
+/// with `PlayerSettings::turn_acceleration`.
+#[derive(Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(default))]
+pub struct FlightSettings {
+    /// Radians per second the player pitches at with the pitch keys
+    pub pitch_speed: f32,
+    /// Radians per second the player rolls at with the roll keys
+    pub roll_speed: f32,
+    /// How fast the player rolls back level when not rolling, 0 to keep its roll
+    pub auto_level: f32,
+}
+
+impl Default for FlightSettings {
+    fn default() -> Self {
+        Self {
+            pitch_speed: 2.,
+            roll_speed: 3.,
+            auto_level: 0.,
+        }
+    }
+}
+
+/// Velocity of a player, added to it once it moves
+#[derive(Component, Clone, Copy, Debug, Default)]
+pub struct PlayerVelocity {
+    pub velocity: Vec3,
+    /// Radians per second the player turns left at, around its own up axis with the `Flight` movement
+    pub angular_velocity: f32,
+    /// Radians per second the player pitches up at with the `Flight` movement
+    pub pitch_velocity: f32,
+    /// Radians per second the player rolls left at with the `Flight` movement
+    pub roll_velocity: f32,
+    /// Whether the player stands on the ground, always `false` for the `Fly` movement
+    pub grounded: bool,
+    /// Radians the player is rolled into a turn by the `Vehicle` movement
+    pub bank: f32,
+}
+
+impl PlayerVelocity {
+    /// Steps the velocity towards the wished direction along the ground, applies gravity and
+    /// jumping, and returns the new translation, standing on `ground` or else the plane at height 0
+    #[allow(clippy::too_many_arguments)]
+    pub(crate) fn step(
+        &mut self,
+        settings: &GroundedSettings,
+        ground: Option<&PlayerGround>,
+        max_speed: f32,
+        wish: Vec3,
+        jump: bool,
+        sprint: bool,
+        translation: Vec3,
+        delta_seconds: f32,
+    ) -> Vec3 {
+        let wish = Vec3::new(wish.x, 0., wish.z).clamp_length_max(1.);
+        let max_speed = if sprint {
+            max_speed * settings.sprint
+        } else {
+            max_speed
+        };
+        let rate = if wish == Vec3::ZERO {
+            settings.deceleration
+        } else {
+            settings.acceleration
+        };
+        let horizontal = Vec3::new(self.velocity.x, 0., self.velocity.z);
+        let difference = wish * max_speed - horizontal;
+        let horizontal = horizontal + difference.clamp_length_max(rate * delta_seconds);
+
+        let mut vertical = self.velocity.y;
+        if jump && self.grounded {
+            vertical = settings.jump_speed;
+        }
+        vertical -= settings.gravity * delta_seconds;
+
+        self.velocity = Vec3::new(horizontal.x, vertical, horizontal.z);
+        let from = translation;
+        let mut translation = translation + self.velocity * delta_seconds;
+
+        let (from_xz, to_xz) = (
+            Vec2::new(from.x, from.z),
+            Vec2::new(translation.x, translation.z),
+        );
+        if let Some(ground) = ground {
+            if self.grounded && !ground.walkable(from_xz, to_xz) {
+                translation.x = from.x;
+                translation.z = from.z;
+                self.velocity.x = 0.;
+                self.velocity.z = 0.;
+            }
+        }
+
+        let floor = match ground {
+            Some(ground) => ground
+                .ground
+                .height(Vec2::new(translation.x, translation.z))
+                .map(|height| height + settings.height),
+            None => Some(settings.height),
+        };
+        self.grounded = match floor {
+            Some(floor) => {
+                // Stay on the ground when walking down slopes instead of falling down them
+                let max_slope = ground.map_or(0., |ground| ground.max_slope);
+                let step_down = from_xz.distance(to_xz) * max_slope.tan() + 1e-4;
+                let stick =
+                    self.grounded && self.velocity.y <= 0. && translation.y - floor <= step_down;
+                translation.y <= floor || stick
+            }
+            None => false,
+        };
+        if let (true, Some(floor)) = (self.grounded, floor) {
+            translation.y = floor;
+            self.velocity.y = self.velocity.y.max(0.);
+        }
+        translation
+    }
+
+    /// Steps the velocity of a vehicle heading along `yaw` with `throttle` in -1 to 1,
+    /// and returns the new translation, hovering over `ground` or else the plane at height 0
+    #[allow(clippy::too_many_arguments)]
+    pub(crate) fn drive(
+        &mut self,
+        settings: &VehicleSettings,
+        ground: Option<&PlayerGround>,
+        max_speed: f32,
+        throttle: f32,
+        yaw: f32,
+        translation: Vec3,
+        delta_seconds: f32,
+        seconds: f32,
+    ) -> Vec3 {
+        let forward = Quat::from_rotation_y(yaw) * -Vec3::Z;
+        let right = Vec3::new(-forward.z, 0., forward.x);
+        let mut speed = self.velocity.dot(forward);
+        let side = self.velocity.dot(right) * (-settings.grip * delta_seconds).exp();
+
+        let throttle = throttle.clamp(-1., 1.);
+        let (target, rate) = if throttle == 0. {
+            (0., settings.drag)
+        } else if throttle * speed < 0. {
+            (0., settings.brake)
+        } else if throttle > 0. {
+            (throttle * max_speed, settings.acceleration)
+        } else {
+            (
+                throttle * max_speed * settings.reverse,
+                settings.acceleration,
+            )
+        };
+        let max_change = rate * delta_seconds;
+        speed += (target - speed).clamp(-max_change, max_change);
+
+        self.velocity = forward * speed + right * side;
+        self.grounded = true;
+        let from = translation;
+        let mut translation = translation + self.velocity * delta_seconds;
+
+        if let Some(ground) = ground {
+            let to = Vec2::new(translation.x, translation.z);
+            if !ground.walkable(Vec2::new(from.x, from.z), to) {
+                translation = from;
+                self.velocity = Vec3::ZERO;
+            }
+        }
+
+        let floor = ground
+            .and_then(|ground| {
+                ground
+                    .ground
+                    .height(Vec2::new(translation.x, translation.z))
+            })
+            .unwrap_or(0.);
+        let bob = (seconds * settings.bob_frequency * TAU).sin() * settings.bob_height;
+        translation.y = floor + settings.hover_height + bob;
+        translation
+    }
+
+    /// Steps the turning of a flying player towards `spin`, its pitch up, left turn and left roll
+    /// input in -1 to 1, and returns its new rotation
+    pub(crate) fn turn_freely(
+        &mut self,
+        settings: &FlightSettings,
+        turn_speed: f32,
+        turn_acceleration: f32,
+        spin: Vec3,
+        rotation: Quat,
+        delta_seconds: f32,
+    ) -> Quat {
+        let spin = spin.clamp(-Vec3::ONE, Vec3::ONE);
+        let target = spin * Vec3::new(settings.pitch_speed, turn_speed, settings.roll_speed);
+        let current = Vec3::new(
+            self.pitch_velocity,
+            self.angular_velocity,
+            self.roll_velocity,
+        );
+        let max_change = Vec3::splat(turn_acceleration * delta_seconds);
+        let current = current + (target - current).clamp(-max_change, max_change);
+        self.pitch_velocity = current.x;
+        self.angular_velocity = current.y;
+        self.roll_velocity = current.z;
+
+        let mut rotation = rotation * Quat::from_scaled_axis(current * delta_seconds);
+        let (right, up, forward) = (rotation * Vec3::X, rotation * Vec3::Y, rotation * -Vec3::Z);
+        // Which way is level is unclear when facing straight up or down
+        if spin.z == 0. && settings.auto_level > 0. && forward.y.abs() < 0.99 {
+            let roll = right.y.atan2(up.y);
+            let t = 1. - (-settings.auto_level * delta_seconds).exp();
+            rotation *= Quat::from_rotation_z(-roll * t);
+        }
+        rotation.normalize()
+    }
+}
@@ -0,0 +1,83 @@
+use std::f32::consts::FRAC_PI_2;
+
+use bevy::prelude::*;
+
+use crate::{CamLogic, CameraState, Cycle, MovementSettings, PlayerCam, PlayerInput, PlayerMove};
+
+/// Cycles the camera mode of a player with the select button of its gamepad
+pub(crate) fn cycle_player_modes(
+    buttons: Res<Input<GamepadButton>>,
+    mut players: Query<&mut PlayerInput>,
+) {
+    for mut input in players.iter_mut() {
+        let gamepad = match input.gamepad {
+            Some(gamepad) => gamepad,
+            None => continue,
+        };
+        if buttons.just_pressed(GamepadButton(gamepad, GamepadButtonType::Select)) {
+            input.mode = input.mode.as_ref().map(Cycle::next);
+        }
+    }
+}
+
+/// Places the `PlayerCam` of every other player than the plugin's own by its `PlayerInput::mode`
+pub(crate) fn move_player_cams(
+    settings: Res<MovementSettings>,
+    cl: Res<CamLogic>,
+    players: Query<(Entity, &GlobalTransform, &PlayerInput, &Children), With<PlayerMove>>,
+    mut player_cams: Query<(&mut Transform, &GlobalTransform), With<PlayerCam>>,
+) {
+    for (entity, player, input, children) in players.iter() {
+        // The plugin's own player is framed by the camera mode of the `CamController`
+        if cl.player.entity == Some(entity) {
+            continue;
+        }
+        let mode = match &input.mode {
+            Some(mode) => mode,
+            None => continue,
+        };
+        for child in children.iter() {
+            let (mut transform, global) = match player_cams.get_mut(*child) {
+                Ok(cam) => cam,
+                Err(_) => continue,
+            };
+            // Placed in the world rather than relative to the player, turned into a child transform below
+            let world = match mode {
+                CameraState::FollowBehind => {
+                    *transform =
+                        Transform::from_translation(Vec3::new(0., 0.25, 1.) * settings.follow_dist);
+                    continue;
+                }
+                CameraState::TopDownDirection => {
+                    *transform = Transform {
+                        translation: Vec3::Y * settings.dist,
+                        rotation: Quat::from_rotation_x(-FRAC_PI_2),
+                        ..Default::default()
+                    };
+                    continue;
+                }
+                CameraState::Fps => Transform {
+                    translation: player.translation + Vec3::Y,
+                    rotation: player.rotation,
+                    ..Default::default()
+                },
+                CameraState::TopDown => Transform {
+                    translation: player.translation + Vec3::Y * settings.dist,
+                    rotation: Quat::from_rotation_x(-FRAC_PI_2),
+                    ..Default::default()
+                },
+                CameraState::LookAt | CameraState::FollowStatic => {
+                    if global.translation.distance_squared(player.translation) < 1e-6 {
+                        continue;
+                    }
+                    Transform::from_translation(global.translation)
+                        .looking_at(player.translation, Vec3::Y)
+                }
+                // Stays wherever it was put
+                CameraState::Free => continue,
+            };
+            *transform =
+                Transform::from_matrix(player.compute_matrix().inverse() * world.compute_matrix());
+        }
+    }
+}
@@ -0,0 +1,161 @@
+use std::{
+    fmt, fs, io,
+    path::{Path, PathBuf},
+};
+
+use bevy::{app::AppExit, prelude::*, render::camera::PerspectiveProjection};
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    zoom::ZoomTarget, CamController, CamTime, CameraModeChanged, CameraSettingChanged, CameraState,
+    MainFlyCam, MovementSettings, PhotoMode, PlayerSettings,
+};
+
+/// Camera settings that can be saved to and restored from a RON file
+#[derive(Clone, Default, Serialize, Deserialize)]
+#[serde(default)]
+pub struct CamConfig {
+    pub mode: CameraState,
+    /// Field of view of the camera in radians, `None` keeps the default
+    pub fov: Option<f32>,
+    pub movement: MovementSettings,
+    pub player: PlayerSettings,
+}
+
+#[derive(Debug)]
+pub enum CamConfigError {
+    Io(io::Error),
+    Ron(ron::Error),
+}
+
+impl fmt::Display for CamConfigError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            CamConfigError::Io(e) => write!(f, "{}", e),
+            CamConfigError::Ron(e) => write!(f, "{}", e),
+        }
+    }
+}
+
+impl std::error::Error for CamConfigError {}
+
+impl From<io::Error> for CamConfigError {
+    fn from(e: io::Error) -> Self {
+        CamConfigError::Io(e)
+    }
+}
+
+impl From<ron::Error> for CamConfigError {
+    fn from(e: ron::Error) -> Self {
+        CamConfigError::Ron(e)
+    }
+}
+
+impl CamConfig {
+    pub fn load(path: impl AsRef<Path>) -> Result<Self, CamConfigError> {
+        Ok(ron::from_str(&fs::read_to_string(path)?)?)
+    }
+
+    pub fn save(&self, path: impl AsRef<Path>) -> Result<(), CamConfigError> {
+        fs::write(path, ron::ser::to_string_pretty(self, Default::default())?)?;
+        Ok(())
+    }
+}
+
+/// Insert as a resource to restore the camera settings from `path` on startup,
+/// and to save them after the camera mode is switched or a setting is changed with the mouse-scroll
+pub struct CamConfigFile {
+    pub path: PathBuf,
+    pub save_on_change: bool,
+    /// Seconds without further changes before saving, pending changes are also saved on exit
+    pub save_delay: f32,
+}
+
+impl Default for CamConfigFile {
+    fn default() -> Self {
+        Self {
+            path: PathBuf::from("config_cam.ron"),
+            save_on_change: true,
+            save_delay: 1.,
+        }
+    }
+}
+
+pub(crate) fn load_config(
+    file: Option<Res<CamConfigFile>>,
+    mut settings: ResMut<MovementSettings>,
+    mut p_settings: ResMut<PlayerSettings>,
+    mut controller: ResMut<CamController>,
+    mut zoom_target: ResMut<ZoomTarget>,
+) {
+    let file = match file {
+        Some(file) if file.path.exists() => file,
+        _ => return,
+    };
+    match CamConfig::load(&file.path) {
+        Ok(config) => {
+            *settings = config.movement;
+            *p_settings = config.player;
+            // Restoring the mode is not a change worth reporting
+            *controller = CamController::new(config.mode);
+            zoom_target.fov = config.fov;
+        }
+        Err(e) => warn!("Could not load camera config {:?}: {}", file.path, e),
+    }
+}
+
+/// Saves the settings once they have been left alone for `CamConfigFile::save_delay`, or on exit
+#[allow(clippy::too_many_arguments)]
+pub(crate) fn save_config(
+    file: Option<Res<CamConfigFile>>,
+    time: Res<CamTime>,
+    settings: Res<MovementSettings>,
+    p_settings: Res<PlayerSettings>,
+    controller: Res<CamController>,
+    zoom_target: Res<ZoomTarget>,
+    photo: Res<PhotoMode>,
+    projections: Query<&PerspectiveProjection, MainFlyCam>,
+    mut modes: EventReader<CameraModeChanged>,
+    mut changes: EventReader<CameraSettingChanged>,
+    mut exits: EventReader<AppExit>,
+    mut changed_at: Local<Option<f64>>,
+) {
+    let now = time.seconds_since_startup();
+    if modes.iter().count() + changes.iter().count() > 0 {
+        *changed_at = Some(now);
+    }
+    let exiting = exits.iter().count() > 0;
+    let file = match (file, *changed_at) {
+        (Some(file), Some(at))
+            if file.save_on_change && (exiting || now - at >= file.save_delay as f64) =>
+        {
+            file
+        }
+        _ => return,
+    };
+    *changed_at = None;
+
+    // Save where the camera is zooming to rather than where it currently is
+    let mut movement = settings.clone();
+    movement.dist = zoom_target.dist.unwrap_or(movement.dist);
+    movement.follow_dist = zoom_target.follow_dist.unwrap_or(movement.follow_dist);
+    // The photo mode only borrows the camera, save the view it goes back to
+    let (mode, fov) = match photo.returns_to() {
+        Some((mode, fov)) => (mode.clone(), Some(fov)),
+        None => (
+            controller.mode().clone(),
+            zoom_target
+                .fov
+                .or_else(|| projections.get_single().ok().map(|project| project.fov)),
+        ),
+    };
+    let config = CamConfig {
+        mode,
+        fov,
+        movement,
+        player: p_settings.clone(),
+    };
+    if let Err(e) = config.save(&file.path) {
+        warn!("Could not save camera config {:?}: {}", file.path, e);
+    }
+}
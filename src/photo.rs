@@ -0,0 +1,303 @@
+use bevy::{
+    ecs::schedule::ShouldRun,
+    prelude::*,
+    render::camera::{ActiveCamera, Camera, Camera3d, CameraProjection, PerspectiveProjection},
+    window::Windows,
+};
+
+use crate::{
+    validate_key,
+    window::{cursor_state, window_id},
+    zoom::ZoomTarget,
+    CamController, CamTime, CamWindow, CameraState, InputState, MainFlyCam, MovementSettings,
+    PlayerMove, ScrollType,
+};
+
+/// Behaviour of the photo mode, entered and left with `CamKeyMap::photo_mode`
+#[derive(Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(default))]
+pub struct PhotoSettings {
+    /// Pause the player while taking photos, gate your own gameplay with `gameplay_running`
+    pub freeze_time: bool,
+    /// Closest the photo camera gets to a player, 0 lets it pass through
+    pub collision_radius: f32,
+    /// Hide the entities marked with `HideInPhotoMode` when entering the photo mode
+    pub hide_ui: bool,
+}
+
+impl Default for PhotoSettings {
+    fn default() -> Self {
+        Self {
+            freeze_time: true,
+            collision_radius: 1.,
+            hide_ui: true,
+        }
+    }
+}
+
+/// Camera parameters of the photo mode, on the fly cam while the photo mode is on
+///
+/// Bevy doesn't render depth of field or exposure itself, read them in your own post-processing.
+/// The field of view is zoomed with the mouse-scroll and kept on the camera's projection.
+#[derive(Component, Clone, Debug, PartialEq)]
+pub struct PhotoParams {
+    /// Radians the camera is rolled by with the roll keys,
+    /// an `unconstrained` free camera is rolled directly instead
+    pub roll: f32,
+    /// Distance from the camera that is in focus
+    pub focus_distance: f32,
+    /// F-number of the lens, lower values blur more outside the focus distance
+    pub aperture: f32,
+    /// Exposure compensation in stops
+    pub exposure: f32,
+}
+
+impl Default for PhotoParams {
+    fn default() -> Self {
+        Self {
+            roll: 0.,
+            focus_distance: 10.,
+            aperture: 2.8,
+            exposure: 0.,
+        }
+    }
+}
+
+/// Add to the UI entities to hide while the photo mode hides the UI
+///
+/// Visibility isn't inherited by children, mark every entity that should be hidden.
+#[derive(Component, Clone, Copy, Debug, Default)]
+pub struct HideInPhotoMode;
+
+/// Send to enter or leave the photo mode from code
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct TogglePhotoMode;
+
+/// Where the camera was before the photo mode was entered
+struct PreviousView {
+    mode: CameraState,
+    scroll_type: ScrollType,
+    transform: Transform,
+    look: Option<(f32, f32)>,
+    fov: f32,
+}
+
+/// Whether the photo mode is on
+#[derive(Default)]
+pub struct PhotoMode {
+    previous: Option<PreviousView>,
+    frozen: bool,
+    ui_hidden: bool,
+    /// Entities hidden for the photo mode, to show again
+    hidden: Vec<Entity>,
+}
+
+impl PhotoMode {
+    pub fn active(&self) -> bool {
+        self.previous.is_some()
+    }
+
+    /// Whether gameplay is paused for the photo mode
+    pub fn frozen(&self) -> bool {
+        self.frozen
+    }
+
+    pub fn ui_hidden(&self) -> bool {
+        self.ui_hidden
+    }
+
+    /// Mode and field of view the camera goes back to when the photo mode is left
+    #[cfg(feature = "serde")]
+    pub(crate) fn returns_to(&self) -> Option<(&CameraState, f32)> {
+        self.previous
+            .as_ref()
+            .map(|previous| (&previous.mode, previous.fov))
+    }
+}
+
+/// Run criteria pausing a `SystemSet` while the photo mode freezes time
+///
+/// ```
+/// # use bevy::prelude::*;
+/// # use bevy_config_cam::*;
+/// # fn move_enemies() {}
+/// App::new().add_system_set(
+///     SystemSet::new()
+///         .with_run_criteria(gameplay_running)
+///         .with_system(move_enemies),
+/// );
+/// ```
+pub fn gameplay_running(photo: Option<Res<PhotoMode>>) -> ShouldRun {
+    if photo.is_some_and(|photo| photo.frozen()) {
+        ShouldRun::No
+    } else {
+        ShouldRun::Yes
+    }
+}
+
+fn set_ui_hidden(
+    photo: &mut PhotoMode,
+    hide: bool,
+    ui: &mut Query<(Entity, &mut Visibility), With<HideInPhotoMode>>,
+) {
+    photo.ui_hidden = hide;
+    if hide {
+        for (entity, mut visibility) in ui.iter_mut() {
+            // Leave what was already hidden hidden when showing the UI again
+            if visibility.is_visible {
+                visibility.is_visible = false;
+                photo.hidden.push(entity);
+            }
+        }
+    } else {
+        for entity in photo.hidden.drain(..) {
+            if let Ok((_, mut visibility)) = ui.get_mut(entity) {
+                visibility.is_visible = true;
+            }
+        }
+    }
+}
+
+/// Enters and leaves the photo mode, detaching the fly cam from the current view
+#[allow(clippy::too_many_arguments, clippy::type_complexity)]
+pub(crate) fn toggle_photo_mode(
+    keys: Res<Input<KeyCode>>,
+    mut toggles: EventReader<TogglePhotoMode>,
+    mut photo: ResMut<PhotoMode>,
+    mut controller: ResMut<CamController>,
+    settings: Res<MovementSettings>,
+    act_cams: Res<ActiveCamera<Camera3d>>,
+    mut input: ResMut<InputState>,
+    mut zoom_target: ResMut<ZoomTarget>,
+    mut commands: Commands,
+    globals: Query<&GlobalTransform>,
+    mut fly_cams: Query<
+        (
+            Entity,
+            &mut Transform,
+            &mut Camera,
+            &mut PerspectiveProjection,
+        ),
+        MainFlyCam,
+    >,
+    mut ui: Query<(Entity, &mut Visibility), With<HideInPhotoMode>>,
+) {
+    let toggled = keys
+        .get_just_pressed()
+        .any(|key| validate_key(&settings.map.photo_mode, key));
+    // Every event toggles, an even number of them cancel out
+    let toggled = toggled ^ (toggles.iter().count() % 2 == 1);
+
+    if photo.active()
+        && keys
+            .get_just_pressed()
+            .any(|key| validate_key(&settings.map.toggle_ui, key))
+    {
+        let hide = !photo.ui_hidden();
+        set_ui_hidden(&mut photo, hide, &mut ui);
+    }
+    if !toggled {
+        return;
+    }
+    let (fly_cam, mut transform, mut camera, mut projection) = match fly_cams.iter_mut().next() {
+        Some(fly_cam) => fly_cam,
+        None => return,
+    };
+
+    match photo.previous.take() {
+        None => {
+            photo.previous = Some(PreviousView {
+                mode: controller.mode().clone(),
+                scroll_type: controller.scroll_type().clone(),
+                transform: *transform,
+                look: input.looks.get(&fly_cam).copied(),
+                fov: zoom_target.fov.unwrap_or(projection.fov),
+            });
+
+            // Start from what the active camera, maybe the player cam, is showing
+            if let Some(view) = act_cams.get().and_then(|view| globals.get(view).ok()) {
+                transform.translation = view.translation;
+                transform.rotation = view.rotation;
+            }
+            let (yaw, pitch, _) = transform.rotation.to_euler(EulerRot::YXZ);
+            input.looks.insert(fly_cam, (pitch.clamp(-1.54, 1.54), yaw));
+
+            controller.set_mode(CameraState::Free);
+            controller.set_scroll_type(ScrollType::Zoom);
+            commands.entity(fly_cam).insert(PhotoParams::default());
+            photo.frozen = settings.photo.freeze_time;
+            if settings.photo.hide_ui {
+                set_ui_hidden(&mut photo, true, &mut ui);
+            }
+        }
+        Some(previous) => {
+            controller.set_mode(previous.mode);
+            controller.set_scroll_type(previous.scroll_type);
+            *transform = previous.transform;
+            match previous.look {
+                Some(look) => input.looks.insert(fly_cam, look),
+                None => input.looks.remove(&fly_cam),
+            };
+
+            zoom_target.fov = Some(previous.fov);
+            projection.fov = previous.fov;
+            camera.projection_matrix = projection.get_projection_matrix();
+            camera.depth_calculation = projection.depth_calculation();
+
+            commands.entity(fly_cam).remove::<PhotoParams>();
+            photo.frozen = false;
+            set_ui_hidden(&mut photo, false, &mut ui);
+        }
+    }
+}
+
+/// Rolls the photo camera and keeps it out of the players
+#[allow(clippy::too_many_arguments)]
+pub(crate) fn photo_camera(
+    keys: Res<Input<KeyCode>>,
+    time: Res<CamTime>,
+    windows: Option<Res<Windows>>,
+    cam_window: Option<Res<CamWindow>>,
+    photo: Res<PhotoMode>,
+    settings: Res<MovementSettings>,
+    input: Res<InputState>,
+    players: Query<&GlobalTransform, With<PlayerMove>>,
+    mut fly_cams: Query<(Entity, &mut Transform, &mut PhotoParams), MainFlyCam>,
+) {
+    if !photo.active() {
+        return;
+    }
+    let (cursor_locked, _) = cursor_state(&windows, window_id(None, &cam_window));
+    for (entity, mut transform, mut params) in fly_cams.iter_mut() {
+        if !settings.unconstrained {
+            let mut roll = 0.;
+            for key in keys.get_pressed() {
+                if cursor_locked && validate_key(&settings.map.roll_left, key) {
+                    roll += 1.;
+                }
+                if cursor_locked && validate_key(&settings.map.roll_right, key) {
+                    roll -= 1.;
+                }
+            }
+            params.roll += roll * settings.roll_speed * time.delta_seconds();
+
+            let (pitch, yaw) = input.looks.get(&entity).copied().unwrap_or_default();
+            transform.rotation = Quat::from_rotation_y(yaw)
+                * Quat::from_rotation_x(pitch)
+                * Quat::from_rotation_z(params.roll);
+        }
+
+        let radius = settings.photo.collision_radius;
+        if radius <= 0. {
+            continue;
+        }
+        for player in players.iter() {
+            let offset = transform.translation - player.translation;
+            if offset.length() < radius {
+                transform.translation =
+                    player.translation + offset.try_normalize().unwrap_or(Vec3::Y) * radius;
+            }
+        }
+    }
+}
@@ -0,0 +1,250 @@
+use bevy::{
+    prelude::*,
+    render::camera::{ActiveCamera, Camera3d, PerspectiveProjection},
+};
+
+use crate::{
+    validate_key, zoom::ZoomTarget, CamController, CamLogic, CamTime, CameraFraming, CameraState,
+    FlyCam, Framing, MainFlyCam, MovementSettings, PhotoMode, PlayerCam, PlayerMove,
+};
+
+/// A named bundle of camera settings that can be switched to at runtime
+#[derive(Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(default))]
+pub struct CamPreset {
+    pub name: String,
+    pub mode: CameraState,
+    /// Field of view in radians
+    pub fov: f32,
+    /// Speeds, distances and smoothing of the preset
+    pub movement: PresetMovement,
+    /// Framing used for the preset's mode, `None` keeps the current framing
+    pub framing: Option<Framing>,
+    /// Keys that switch to this preset
+    pub keys: Vec<KeyCode>,
+}
+
+impl Default for CamPreset {
+    fn default() -> Self {
+        Self {
+            name: String::new(),
+            mode: CameraState::default(),
+            fov: PerspectiveProjection::default().fov,
+            movement: PresetMovement::default(),
+            framing: None,
+            keys: Vec::new(),
+        }
+    }
+}
+
+impl CamPreset {
+    pub fn new(name: impl Into<String>, mode: CameraState) -> Self {
+        Self {
+            name: name.into(),
+            mode,
+            ..Default::default()
+        }
+    }
+}
+
+/// The `MovementSettings` a preset sets, the key bindings and everything else are left as they are
+#[derive(Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(default))]
+pub struct PresetMovement {
+    pub speed: f32,
+    pub sensitivity: f32,
+    pub dist: f32,
+    pub follow_dist: f32,
+    pub lerp: f32,
+    /// Free camera acceleration and deceleration, see `MovementSettings::acceleration`
+    pub acceleration: f32,
+    pub deceleration: f32,
+    /// See `ZoomSettings::smoothing`
+    pub zoom_smoothing: f32,
+}
+
+impl Default for PresetMovement {
+    fn default() -> Self {
+        let settings = MovementSettings::default();
+        Self {
+            speed: settings.speed,
+            sensitivity: settings.sensitivity,
+            dist: settings.dist,
+            follow_dist: settings.follow_dist,
+            lerp: settings.lerp,
+            acceleration: settings.acceleration,
+            deceleration: settings.deceleration,
+            zoom_smoothing: settings.zoom.smoothing,
+        }
+    }
+}
+
+/// The presets that can be switched to with their keys or a `SwitchCamPreset` event
+#[derive(Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(default))]
+pub struct CamPresets {
+    pub presets: Vec<CamPreset>,
+    /// Seconds the camera takes to blend into a newly selected preset
+    pub blend: f32,
+}
+
+impl Default for CamPresets {
+    fn default() -> Self {
+        Self {
+            presets: Vec::new(),
+            blend: 1.,
+        }
+    }
+}
+
+impl CamPresets {
+    pub fn get(&self, name: &str) -> Option<&CamPreset> {
+        self.presets.iter().find(|preset| preset.name == name)
+    }
+}
+
+/// Send to switch to the preset with the given name
+#[derive(Clone, Debug, PartialEq)]
+pub struct SwitchCamPreset(pub String);
+
+/// Where the camera was when the preset was switched and how far it has blended away from it
+#[derive(Default)]
+pub(crate) struct PresetBlend {
+    from: Option<Transform>,
+    elapsed: f32,
+    duration: f32,
+}
+
+pub(crate) fn preset_hotkeys(
+    keys: Res<Input<KeyCode>>,
+    presets: Res<CamPresets>,
+    photo: Res<PhotoMode>,
+    mut switches: EventWriter<SwitchCamPreset>,
+) {
+    // The photo mode keeps the free camera until it is left
+    if photo.active() {
+        return;
+    }
+    for key in keys.get_just_pressed() {
+        for preset in presets
+            .presets
+            .iter()
+            .filter(|preset| validate_key(&preset.keys, key))
+        {
+            switches.send(SwitchCamPreset(preset.name.clone()));
+        }
+    }
+}
+
+#[allow(clippy::too_many_arguments)]
+pub(crate) fn switch_preset(
+    presets: Res<CamPresets>,
+    photo: Res<PhotoMode>,
+    mut switches: EventReader<SwitchCamPreset>,
+    mut controller: ResMut<CamController>,
+    mut settings: ResMut<MovementSettings>,
+    mut framing: ResMut<CameraFraming>,
+    mut zoom_target: ResMut<ZoomTarget>,
+    mut blend: ResMut<PresetBlend>,
+    act_cams: Res<ActiveCamera<Camera3d>>,
+    cameras: Query<&GlobalTransform, With<Camera3d>>,
+) {
+    for SwitchCamPreset(name) in switches.iter() {
+        if photo.active() {
+            warn!(
+                "Not switching to camera preset {:?} in the photo mode",
+                name
+            );
+            continue;
+        }
+        let preset = match presets.get(name) {
+            Some(preset) => preset,
+            None => {
+                warn!("No camera preset named {:?}", name);
+                continue;
+            }
+        };
+
+        controller.set_mode(preset.mode.clone());
+
+        let movement = &preset.movement;
+        settings.speed = movement.speed;
+        settings.sensitivity = movement.sensitivity;
+        settings.lerp = movement.lerp;
+        settings.acceleration = movement.acceleration;
+        settings.deceleration = movement.deceleration;
+        settings.zoom.smoothing = movement.zoom_smoothing;
+        // Distances and FOV are blended by zooming towards them
+        zoom_target.dist = Some(preset.movement.dist);
+        zoom_target.follow_dist = Some(preset.movement.follow_dist);
+        zoom_target.fov = Some(preset.fov);
+
+        if let Some(preset_framing) = preset.framing {
+            framing.modes.insert(preset.mode.clone(), preset_framing);
+        }
+
+        if let Some(from) = act_cams.get().and_then(|cam| cameras.get(cam).ok()) {
+            *blend = PresetBlend {
+                from: Some(Transform::from(*from)),
+                elapsed: 0.,
+                duration: presets.blend,
+            };
+        }
+    }
+}
+
+/// Moves the fly cam from where the camera was to where the new preset puts it
+#[allow(clippy::type_complexity, clippy::too_many_arguments)]
+pub(crate) fn blend_preset(
+    time: Res<CamTime>,
+    settings: Res<MovementSettings>,
+    mut blend: ResMut<PresetBlend>,
+    mut act_cams: ResMut<ActiveCamera<Camera3d>>,
+    cl: Res<CamLogic>,
+    players: Query<&Transform, (With<PlayerMove>, Without<FlyCam>)>,
+    player_cams: Query<(Entity, &Transform, &Parent), (With<PlayerCam>, Without<FlyCam>)>,
+    mut fly_cams: Query<(Entity, &mut Transform), MainFlyCam>,
+) {
+    let from = match blend.from {
+        Some(from) => from,
+        None => return,
+    };
+    let (fly_cam, mut fly_transform) = match fly_cams.get_single_mut() {
+        Ok(fly_cam) => fly_cam,
+        Err(_) => return,
+    };
+
+    let player = cl.player.entity;
+    let (target, target_cam) = match (
+        settings.locked_to_player,
+        player.and_then(|player| players.get(player).ok()),
+        player_cams
+            .iter()
+            .find(|(_, _, parent)| Some(parent.0) == player),
+    ) {
+        (true, Some(player), Some((player_cam, cam_transform, _))) => {
+            (player.mul_transform(*cam_transform), player_cam)
+        }
+        _ => (*fly_transform, fly_cam),
+    };
+
+    blend.elapsed += time.delta_seconds();
+    if blend.duration <= 0. || blend.elapsed >= blend.duration {
+        blend.from = None;
+        *fly_transform = target;
+        act_cams.set(target_cam);
+        return;
+    }
+
+    let t = blend.elapsed / blend.duration;
+    let t = t * t * (3. - 2. * t);
+    *fly_transform = Transform {
+        translation: from.translation.lerp(target.translation, t),
+        rotation: from.rotation.slerp(target.rotation, t),
+        scale: target.scale,
+    };
+    act_cams.set(fly_cam);
+}
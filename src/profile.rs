@@ -0,0 +1,76 @@
+use bevy::{
+    asset::{AssetLoader, BoxedFuture, LoadContext, LoadedAsset},
+    prelude::*,
+    reflect::TypeUuid,
+};
+use serde::{Deserialize, Serialize};
+
+use crate::{zoom::ZoomTarget, MovementSettings, PlayerSettings};
+
+/// Camera configuration loaded from a `.camprofile.ron` asset
+///
+/// Changes to `player_asset` and `pos` only take effect when the player is spawned.
+#[derive(Clone, Default, Serialize, Deserialize, TypeUuid)]
+#[uuid = "6a3f0b7e-2d4c-4e0b-9a51-7c8e2f1d9b34"]
+#[serde(default)]
+pub struct CamProfile {
+    pub movement: MovementSettings,
+    pub player: PlayerSettings,
+}
+
+#[derive(Default)]
+pub struct CamProfileLoader;
+
+impl AssetLoader for CamProfileLoader {
+    fn load<'a>(
+        &'a self,
+        bytes: &'a [u8],
+        load_context: &'a mut LoadContext,
+    ) -> BoxedFuture<'a, Result<(), anyhow::Error>> {
+        Box::pin(async move {
+            let profile: CamProfile = ron::de::from_bytes(bytes)?;
+            load_context.set_default_asset(LoadedAsset::new(profile));
+            Ok(())
+        })
+    }
+
+    fn extensions(&self) -> &[&str] {
+        &["camprofile.ron"]
+    }
+}
+
+/// Insert with a handle to a `.camprofile.ron` asset to drive the camera settings,
+/// they are updated every time the asset is (re)loaded
+pub struct ActiveCamProfile(pub Handle<CamProfile>);
+
+pub(crate) fn apply_cam_profile(
+    active: Option<Res<ActiveCamProfile>>,
+    profiles: Res<Assets<CamProfile>>,
+    mut events: EventReader<AssetEvent<CamProfile>>,
+    mut settings: ResMut<MovementSettings>,
+    mut p_settings: ResMut<PlayerSettings>,
+    mut zoom_target: ResMut<ZoomTarget>,
+) {
+    let active = match active {
+        Some(active) => active,
+        None => return,
+    };
+    // A newly inserted profile that finished loading earlier has no event left to react to
+    let mut apply = active.is_changed();
+    for event in events.iter() {
+        if let AssetEvent::Created { handle } | AssetEvent::Modified { handle } = event {
+            apply |= *handle == active.0;
+        }
+    }
+
+    if let (true, Some(profile)) = (apply, profiles.get(&active.0)) {
+        // Keep track of which camera is active, the rest is recalculated every frame
+        let ltp = settings.ltp;
+        *settings = profile.movement.clone();
+        settings.ltp = ltp;
+        *p_settings = profile.player.clone();
+        // Zoom from the profile's distances instead of back to the previous zoom target
+        zoom_target.dist = Some(settings.dist);
+        zoom_target.follow_dist = Some(settings.follow_dist);
+    }
+}
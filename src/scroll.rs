@@ -0,0 +1,64 @@
+use bevy::input::mouse::{MouseScrollUnit, MouseWheel};
+
+/// Step size and bounds of a setting adjusted with the mouse-scroll
+#[derive(Clone, Copy, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct ScrollStep {
+    /// Change per scrolled line
+    pub step: f32,
+    pub min: f32,
+    pub max: f32,
+}
+
+impl ScrollStep {
+    pub fn new(step: f32, min: f32, max: f32) -> Self {
+        Self { step, min, max }
+    }
+
+    pub fn apply(&self, value: f32, lines: f32) -> f32 {
+        clamp_loose(value + lines * self.step, self.min, self.max)
+    }
+}
+
+/// `f32::clamp` that doesn't panic on bounds from a config file, where `min` can end up above
+/// `max` or be NaN, `min` wins over `max` and NaN bounds are ignored
+pub(crate) fn clamp_loose(value: f32, min: f32, max: f32) -> f32 {
+    value.min(max).max(min)
+}
+
+/// How mouse-scroll events are turned into changes of the selected setting
+///
+/// Mouse wheels report scrolling in lines while trackpads report it in pixels,
+/// both are converted to lines using the multipliers before the step is applied.
+#[derive(Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(default))]
+pub struct ScrollSettings {
+    pub line_multiplier: f32,
+    pub pixel_multiplier: f32,
+    pub speed: ScrollStep,
+    pub sensitivity: ScrollStep,
+    pub lerp: ScrollStep,
+}
+
+impl Default for ScrollSettings {
+    fn default() -> Self {
+        Self {
+            line_multiplier: 1.,
+            pixel_multiplier: 0.05,
+            speed: ScrollStep::new(0.1, 0., 100.),
+            sensitivity: ScrollStep::new(0.000001, 0., 0.001),
+            lerp: ScrollStep::new(0.01, 0., 1.),
+        }
+    }
+}
+
+impl ScrollSettings {
+    /// Amount of lines scrolled by the event
+    pub fn lines(&self, event: &MouseWheel) -> f32 {
+        match event.unit {
+            MouseScrollUnit::Line => event.y * self.line_multiplier,
+            MouseScrollUnit::Pixel => event.y * self.pixel_multiplier,
+        }
+    }
+}
@@ -0,0 +1,34 @@
+use bevy::prelude::*;
+
+/// Clock the plugin's systems advance by, following bevy's `Time` unless `fixed_delta` is set
+///
+/// Insert `CamTime::fixed` before adding the plugin to step every frame by exactly that many
+/// seconds, for tests or replays that need the camera and player to move deterministically.
+#[derive(Clone, Debug, Default)]
+pub struct CamTime {
+    pub fixed_delta: Option<f32>,
+    delta: f32,
+    elapsed: f64,
+}
+
+impl CamTime {
+    pub fn fixed(delta_seconds: f32) -> Self {
+        Self {
+            fixed_delta: Some(delta_seconds),
+            ..Default::default()
+        }
+    }
+
+    pub fn delta_seconds(&self) -> f32 {
+        self.delta
+    }
+
+    pub fn seconds_since_startup(&self) -> f64 {
+        self.elapsed
+    }
+}
+
+pub(crate) fn update_cam_time(time: Res<Time>, mut cam_time: ResMut<CamTime>) {
+    cam_time.delta = cam_time.fixed_delta.unwrap_or_else(|| time.delta_seconds());
+    cam_time.elapsed += cam_time.delta as f64;
+}
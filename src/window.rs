@@ -0,0 +1,84 @@
+use bevy::{
+    prelude::*,
+    window::{WindowId, Windows},
+};
+
+/// Insert to grab the cursor of, and scale mouse-look by, a window other than the primary one
+///
+/// Added to a `FlyCam` it makes that camera a free camera controlled from the given window,
+/// independently of the camera mode. Input only moves a camera while its window is focused.
+/// Without the targeted window, or without any windows (e.g. when running headless),
+/// keyboard and mouse input always moves the camera.
+#[derive(Component, Clone, Copy, Debug, PartialEq, Eq)]
+pub struct CamWindow(pub WindowId);
+
+impl Default for CamWindow {
+    fn default() -> Self {
+        Self(WindowId::primary())
+    }
+}
+
+/// The window a camera is controlled from, its own `CamWindow` or else the plugin's
+pub(crate) fn window_id(own: Option<&CamWindow>, target: &Option<Res<CamWindow>>) -> WindowId {
+    own.or(target.as_deref()).copied().unwrap_or_default().0
+}
+
+/// Grabs/ungrabs mouse cursor
+fn toggle_grab_cursor(window: &mut Window) {
+    window.set_cursor_lock_mode(!window.cursor_locked());
+    window.set_cursor_visibility(!window.cursor_visible());
+}
+
+/// Whether input should move the camera and the size mouse-look is scaled by
+pub(crate) fn cursor_state(windows: &Option<Res<Windows>>, id: WindowId) -> (bool, Vec2) {
+    match windows.as_ref().and_then(|w| w.get(id)) {
+        Some(window) => (
+            window.cursor_locked() && window.is_focused(),
+            Vec2::new(window.width(), window.height()),
+        ),
+        None => {
+            let descriptor = WindowDescriptor::default();
+            (true, Vec2::new(descriptor.width, descriptor.height))
+        }
+    }
+}
+
+/// Grabs the cursor once the window the camera is controlled from exists
+pub(crate) fn initial_grab_cursor(
+    mut grabbed: Local<bool>,
+    windows: Option<ResMut<Windows>>,
+    target: Option<Res<CamWindow>>,
+) {
+    if *grabbed {
+        return;
+    }
+    if let Some(window) = windows.and_then(|w| w.into_inner().get_mut(window_id(None, &target))) {
+        toggle_grab_cursor(window);
+        *grabbed = true;
+    }
+}
+
+/// Toggles the grab of the focused window, as long as a camera is controlled from it
+pub(crate) fn cursor_grab(
+    keys: Res<Input<KeyCode>>,
+    windows: Option<ResMut<Windows>>,
+    target: Option<Res<CamWindow>>,
+    own: Query<&CamWindow>,
+) {
+    if !keys.just_pressed(KeyCode::Escape) {
+        return;
+    }
+    let mut windows = match windows {
+        Some(windows) => windows,
+        None => return,
+    };
+    let main = window_id(None, &target);
+    let focused = windows
+        .iter()
+        .find(|window| window.is_focused())
+        .map(|window| window.id())
+        .filter(|id| *id == main || own.iter().any(|own| own.0 == *id));
+    if let Some(window) = focused.and_then(|id| windows.get_mut(id)) {
+        toggle_grab_cursor(window);
+    }
+}
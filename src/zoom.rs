@@ -0,0 +1,96 @@
+use bevy::{
+    prelude::*,
+    render::camera::{Camera, CameraProjection, PerspectiveProjection},
+};
+
+use crate::{scroll::clamp_loose, CamTime, MainFlyCam, MovementSettings};
+
+/// Limits and smoothing of the `Zoom` scroll setting
+#[derive(Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(default))]
+pub struct ZoomSettings {
+    /// Bounds of the field of view in radians, used by the perspective modes
+    pub min_fov: f32,
+    pub max_fov: f32,
+    /// Bounds of the camera distance, used by the follow and top-down modes
+    pub min_dist: f32,
+    pub max_dist: f32,
+    /// Fraction of the current value zoomed per scrolled line
+    pub step: f32,
+    /// How fast the camera reaches the zoom target, per second
+    pub smoothing: f32,
+}
+
+impl Default for ZoomSettings {
+    fn default() -> Self {
+        Self {
+            min_fov: 10f32.to_radians(),
+            max_fov: 120f32.to_radians(),
+            min_dist: 1.,
+            max_dist: 50.,
+            step: 0.1,
+            smoothing: 10.,
+        }
+    }
+}
+
+impl ZoomSettings {
+    // Zooming is multiplicative so every line feels the same regardless of the current value
+    fn zoom(&self, value: f32, lines: f32) -> f32 {
+        value * (-self.step * lines).exp()
+    }
+
+    pub(crate) fn zoom_fov(&self, fov: f32, lines: f32) -> f32 {
+        clamp_loose(self.zoom(fov, lines), self.min_fov, self.max_fov)
+    }
+
+    pub(crate) fn zoom_dist(&self, dist: f32, lines: f32) -> f32 {
+        clamp_loose(self.zoom(dist, lines), self.min_dist, self.max_dist)
+    }
+
+    fn smooth(&self, delta_seconds: f32) -> f32 {
+        if self.smoothing.is_infinite() {
+            1.0
+        } else {
+            1.0 - (-self.smoothing * delta_seconds).exp()
+        }
+    }
+}
+
+/// Values the camera is zooming towards
+#[derive(Default)]
+pub(crate) struct ZoomTarget {
+    pub fov: Option<f32>,
+    pub dist: Option<f32>,
+    pub follow_dist: Option<f32>,
+}
+
+/// Interpolates the camera towards the zoom target
+pub(crate) fn zoom(
+    time: Res<CamTime>,
+    mut settings: ResMut<MovementSettings>,
+    target: Res<ZoomTarget>,
+    mut query: Query<(&mut Camera, &mut PerspectiveProjection), MainFlyCam>,
+) {
+    let t = settings.zoom.smooth(time.delta_seconds());
+
+    if let Some(dist) = target.dist {
+        settings.dist += (dist - settings.dist) * t;
+    }
+    if let Some(follow_dist) = target.follow_dist {
+        settings.follow_dist += (follow_dist - settings.follow_dist) * t;
+    }
+    if let Some(fov) = target.fov {
+        for (mut camera, mut project) in query.iter_mut() {
+            if project.fov == fov {
+                continue;
+            }
+            project.fov += (fov - project.fov) * t;
+
+            //Update camera with the new fov
+            camera.projection_matrix = project.get_projection_matrix();
+            camera.depth_calculation = project.depth_calculation();
+        }
+    }
+}
@@ -0,0 +1,824 @@
+mod common;
+
+use std::f32::consts::FRAC_PI_2;
+
+use bevy::{input::gamepad::GamepadEventRaw, prelude::*, window::WindowId};
+use bevy_config_cam::*;
+use common::*;
+
+#[test]
+fn free_cam_moves_along_the_ground() {
+    let mut test = TestApp::new(CameraState::Free);
+    let start = test.fly_cam();
+
+    test.press(KeyCode::W);
+    let elapsed = test.run(10);
+
+    let local_z = start.local_z();
+    let forward = -Vec3::new(local_z.x, 0., local_z.z).normalize();
+    let speed = test.settings().speed;
+    assert_near(
+        test.fly_cam().translation,
+        start.translation + forward * speed * elapsed,
+    );
+}
+
+#[test]
+fn free_cam_flies_where_it_looks() {
+    let mut test = TestApp::with_resources(CameraState::Free, |app| {
+        app.insert_resource(MovementSettings {
+            look_flight: true,
+            camera_up: true,
+            ..Default::default()
+        });
+    });
+    let start = test.fly_cam();
+    let speed = test.settings().speed;
+
+    test.press(KeyCode::W);
+    let elapsed = test.run(5);
+    assert_near(
+        test.fly_cam().translation,
+        start.translation + start.forward() * speed * elapsed,
+    );
+
+    test.release(KeyCode::W);
+    test.press(KeyCode::Space);
+    let from = test.fly_cam().translation;
+    let elapsed = test.run(5);
+    assert_near(
+        test.fly_cam().translation,
+        from + start.up() * speed * elapsed,
+    );
+}
+
+#[test]
+fn free_cam_boosts_and_slows_down() {
+    let mut test = TestApp::new(CameraState::Free);
+    let speed = test.settings().speed;
+    let boost = test.settings().boost;
+    let slow = test.settings().slow;
+
+    test.press(KeyCode::W);
+    test.press(KeyCode::LAlt);
+    let start = test.fly_cam().translation;
+    let elapsed = test.run(5);
+    let moved = test.fly_cam().translation.distance(start);
+    assert!((moved - speed * boost * elapsed).abs() < 1e-3);
+
+    test.release(KeyCode::LAlt);
+    test.press(KeyCode::LControl);
+    let start = test.fly_cam().translation;
+    let elapsed = test.run(5);
+    let moved = test.fly_cam().translation.distance(start);
+    assert!((moved - speed * slow * elapsed).abs() < 1e-3);
+}
+
+#[test]
+fn free_cam_glides_to_a_stop() {
+    let mut test = TestApp::with_resources(CameraState::Free, |app| {
+        app.insert_resource(MovementSettings {
+            acceleration: 0.1,
+            deceleration: 0.1,
+            ..Default::default()
+        });
+    });
+    let speed = test.settings().speed;
+
+    let start = test.fly_cam().translation;
+    test.press(KeyCode::W);
+    let elapsed = test.run(5);
+    // Still speeding up
+    assert!(test.fly_cam().translation.distance(start) < speed * elapsed - 1e-3);
+
+    test.run_for(0.5);
+    test.release(KeyCode::W);
+    test.step();
+    let released = test.fly_cam().translation;
+    test.run(5);
+    assert!(test.fly_cam().translation.distance(released) > 0.);
+
+    test.run_for(3.);
+    let stopped = test.fly_cam().translation;
+    test.run(5);
+    assert_near(test.fly_cam().translation, stopped);
+}
+
+#[test]
+fn free_cam_moves_without_the_targeted_window() {
+    let mut test = TestApp::with_resources(CameraState::Free, |app| {
+        app.insert_resource(CamWindow(WindowId::new()));
+    });
+    let start = test.fly_cam();
+
+    test.press(KeyCode::W);
+    test.run(2);
+
+    assert_ne!(test.fly_cam().translation, start.translation);
+}
+
+#[test]
+fn fly_cam_with_its_own_window_moves_in_any_mode() {
+    let mut test = TestApp::new(CameraState::TopDown);
+    let editor_cam = test
+        .app
+        .world
+        .spawn()
+        .insert_bundle(PerspectiveCameraBundle::default())
+        .insert_bundle((FlyCam, CamWindow(WindowId::new())))
+        .id();
+
+    test.press(KeyCode::W);
+    test.run(2);
+
+    // The plugin's fly cam is placed by the top down mode while the editor cam moves freely
+    let dist = test.settings().dist;
+    let editor = *test.app.world.get::<Transform>(editor_cam).unwrap();
+    assert!(editor.translation.z < 0.);
+    let fly_cam = test.fly_cam();
+    assert_near(
+        fly_cam.translation,
+        test.player().translation + Vec3::Y * dist,
+    );
+}
+
+#[test]
+fn free_cam_looks_with_the_mouse() {
+    let mut test = TestApp::new(CameraState::Free);
+
+    test.mouse_motion(Vec2::new(10., 0.));
+    test.step();
+
+    // Without a window mouse-look is scaled by the default window size
+    let width = WindowDescriptor::default().width;
+    let yaw = -(test.settings().sensitivity * 10. * width).to_radians();
+    assert!(test
+        .fly_cam()
+        .rotation
+        .abs_diff_eq(Quat::from_rotation_y(yaw), 1e-5));
+}
+
+fn unconstrained() -> TestApp {
+    TestApp::with_resources(CameraState::Free, |app| {
+        app.insert_resource(MovementSettings {
+            unconstrained: true,
+            ..Default::default()
+        });
+    })
+}
+
+#[test]
+fn unconstrained_free_cam_loops_over() {
+    let mut test = unconstrained();
+
+    // Well past looking straight up from looking down at the scene
+    test.mouse_motion(Vec2::new(0., -2400.));
+    test.step();
+
+    assert!(test.fly_cam().up().y < 0.);
+}
+
+#[test]
+fn unconstrained_free_cam_rolls() {
+    let mut test = unconstrained();
+
+    test.press(KeyCode::Q);
+    test.run(10);
+
+    // Rolls left, its right side goes up
+    assert!(test.fly_cam().right().y > 0.);
+}
+
+#[test]
+fn follow_behind_uses_the_player_cam() {
+    let mut test = TestApp::new(CameraState::FollowBehind);
+    test.run(2);
+
+    let follow_dist = test.settings().follow_dist;
+    assert_eq!(test.active_camera(), Some(test.player_cam_entity()));
+    assert_near(
+        test.player_cam().translation,
+        Vec3::new(0., 0.25, 1.) * follow_dist,
+    );
+}
+
+#[test]
+fn player_moves_forward() {
+    let mut test = TestApp::new(CameraState::FollowBehind);
+    let start = test.player().translation;
+
+    test.press(KeyCode::Up);
+    let elapsed = test.run(10);
+
+    let speed = test.player_settings().speed;
+    assert_near(
+        test.player().translation,
+        start + Vec3::new(0., 0., -speed * elapsed),
+    );
+}
+
+#[test]
+fn top_down_looks_down_on_the_player() {
+    let mut test = TestApp::new(CameraState::TopDown);
+    test.run(2);
+
+    let dist = test.settings().dist;
+    let fly_cam = test.fly_cam();
+    assert_near(
+        fly_cam.translation,
+        test.player().translation + Vec3::Y * dist,
+    );
+    assert!(fly_cam
+        .rotation
+        .abs_diff_eq(Quat::from_rotation_x(-FRAC_PI_2), 1e-5));
+}
+
+#[test]
+fn top_down_direction_uses_the_player_cam() {
+    let mut test = TestApp::new(CameraState::TopDownDirection);
+    test.run(2);
+
+    let dist = test.settings().dist;
+    assert_eq!(test.active_camera(), Some(test.player_cam_entity()));
+    assert_near(test.player_cam().translation, Vec3::Y * dist);
+}
+
+#[test]
+fn fps_sits_above_the_player() {
+    let mut test = TestApp::new(CameraState::Fps);
+    test.run(2);
+
+    assert!(test.settings().disable_move);
+    assert!(!test.settings().disable_look);
+    assert_near(
+        test.fly_cam().translation,
+        test.player().translation + Vec3::Y,
+    );
+}
+
+fn confined(mode: CameraState) -> TestApp {
+    TestApp::with_resources(mode, |app| {
+        app.insert_resource(CameraConfiner {
+            shape: ConfinerShape::Box {
+                min: Vec2::splat(2.),
+                max: Vec2::splat(4.),
+            },
+            ..Default::default()
+        });
+    })
+}
+
+#[test]
+fn confiner_keeps_the_top_down_camera_inside() {
+    let mut test = confined(CameraState::TopDown);
+    test.run(2);
+
+    let height = test.player().translation.y + test.settings().dist;
+    assert_near(test.fly_cam().translation, Vec3::new(2., height, 2.));
+}
+
+#[test]
+fn confiner_leaves_editor_cameras_free() {
+    let mut test = confined(CameraState::Free);
+    let editor_cam = test
+        .app
+        .world
+        .spawn()
+        .insert_bundle(PerspectiveCameraBundle::default())
+        .insert_bundle((FlyCam, CamWindow(WindowId::new())))
+        .id();
+
+    test.press(KeyCode::W);
+    test.run(2);
+
+    let editor = *test.app.world.get::<Transform>(editor_cam).unwrap();
+    assert!(editor.translation.z < 0.);
+}
+
+#[test]
+fn confiner_leaves_the_fps_camera_by_the_player() {
+    let mut test = confined(CameraState::Fps);
+    test.run(2);
+
+    assert_near(
+        test.fly_cam().translation,
+        test.player().translation + Vec3::Y,
+    );
+}
+
+#[test]
+fn look_at_and_follow_static_turn_towards_the_player() {
+    for mode in [CameraState::LookAt, CameraState::FollowStatic] {
+        let mut test = TestApp::new(mode.clone());
+        let player = Vec3::new(6., 0.5, 0.);
+        test.set_player(player);
+        let start = test.fly_cam();
+        let angle_to_player =
+            |cam: Transform| cam.forward().angle_between(player - cam.translation).abs();
+        assert!(angle_to_player(start) > 0.5, "{:?}", mode);
+
+        test.run_for(1.5);
+
+        let fly_cam = test.fly_cam();
+        assert_near(fly_cam.translation, start.translation);
+        assert!(angle_to_player(fly_cam) < 0.15, "{:?}", mode);
+    }
+}
+
+#[test]
+fn next_cam_key_cycles_the_mode() {
+    let mut test = TestApp::new(CameraState::FollowBehind);
+
+    test.tap(KeyCode::C);
+
+    assert_eq!(test.mode(), CameraState::Fps);
+    assert_eq!(
+        test.events::<CameraModeChanged>(),
+        vec![CameraModeChanged {
+            old: CameraState::FollowBehind,
+            new: CameraState::Fps,
+        }]
+    );
+}
+
+#[test]
+fn scroll_changes_the_selected_setting() {
+    let mut test = TestApp::new(CameraState::FollowBehind);
+    test.step();
+
+    let speed = test.settings().speed;
+    test.mouse_wheel(1.);
+    test.step();
+    assert!(test.settings().speed > speed);
+    assert_eq!(test.events::<CameraSettingChanged>().len(), 1);
+
+    test.tap(KeyCode::E);
+    assert_eq!(
+        test.events::<ScrollTypeChanged>(),
+        vec![ScrollTypeChanged {
+            old: ScrollType::MovementSpeed,
+            new: ScrollType::Zoom,
+        }]
+    );
+
+    let follow_dist = test.settings().follow_dist;
+    test.mouse_wheel(1.);
+    test.run(20);
+    assert!(test.settings().follow_dist < follow_dist);
+}
+
+#[test]
+fn preset_only_sets_its_own_settings() {
+    let mut test = TestApp::with_resources(CameraState::FollowBehind, |app| {
+        app.insert_resource(MovementSettings {
+            map: CamKeyMap {
+                forward: vec![KeyCode::I],
+                ..Default::default()
+            },
+            log: false,
+            ..Default::default()
+        })
+        .insert_resource(CamPresets {
+            presets: vec![CamPreset {
+                movement: PresetMovement {
+                    speed: 30.,
+                    ..Default::default()
+                },
+                keys: vec![KeyCode::Key1],
+                ..CamPreset::new("debug", CameraState::Free)
+            }],
+            blend: 0.,
+        });
+    });
+
+    test.tap(KeyCode::Key1);
+    test.step();
+
+    assert_eq!(test.mode(), CameraState::Free);
+    assert_eq!(test.settings().speed, 30.);
+    assert_eq!(test.settings().map.forward, vec![KeyCode::I]);
+    assert!(!test.settings().log);
+}
+
+#[test]
+fn pixel_scrolling_is_scaled_and_clamped() {
+    let mut test = TestApp::new(CameraState::FollowBehind);
+    test.step();
+    let scroll = test.settings().scroll.clone();
+    let speed = test.settings().speed;
+
+    test.mouse_wheel_pixels(40.);
+    test.step();
+    let expected = speed + 40. * scroll.pixel_multiplier * scroll.speed.step;
+    assert!((test.settings().speed - expected).abs() < 1e-5);
+
+    test.mouse_wheel_pixels(1e6);
+    test.step();
+    assert_eq!(test.settings().speed, scroll.speed.max);
+
+    test.mouse_wheel_pixels(-1e6);
+    test.step();
+    assert_eq!(test.settings().speed, scroll.speed.min);
+}
+
+#[test]
+fn zooming_tolerates_inverted_bounds() {
+    let mut test = TestApp::with_resources(CameraState::FollowBehind, |app| {
+        app.insert_resource(MovementSettings {
+            zoom: ZoomSettings {
+                min_dist: 8.,
+                max_dist: 2.,
+                ..Default::default()
+            },
+            ..Default::default()
+        });
+    });
+    test.app
+        .world
+        .resource_mut::<CamController>()
+        .set_scroll_type(ScrollType::Zoom);
+
+    test.mouse_wheel(1.);
+    test.run(300);
+
+    assert!((test.settings().follow_dist - 8.).abs() < 1e-3);
+}
+
+#[test]
+fn scroll_steps_tolerate_bad_bounds() {
+    assert_eq!(ScrollStep::new(1., 5., 2.).apply(0., 1.), 5.);
+    assert_eq!(ScrollStep::new(1., f32::NAN, 2.).apply(0., 10.), 2.);
+    assert_eq!(ScrollStep::new(1., 0., f32::NAN).apply(0., -10.), 0.);
+}
+
+/// Spawns another player with `input` and a player cam, returning both
+fn spawn_second_player(test: &mut TestApp, input: PlayerInput, cam: Transform) -> (Entity, Entity) {
+    let mut second_cam = None;
+    let second = test
+        .app
+        .world
+        .spawn()
+        .insert_bundle((
+            Transform::from_xyz(3., 0.5, 0.),
+            GlobalTransform::default(),
+            PlayerMove,
+            input,
+        ))
+        .with_children(|parent| {
+            second_cam = Some(
+                parent
+                    .spawn_bundle(PerspectiveCameraBundle {
+                        transform: cam,
+                        ..Default::default()
+                    })
+                    .insert(PlayerCam)
+                    .id(),
+            );
+        })
+        .id();
+    (second, second_cam.unwrap())
+}
+
+#[test]
+fn second_player_has_its_own_controls() {
+    let mut test = TestApp::new(CameraState::FollowBehind);
+    let first = test.player_entity();
+    let first_start = test.player().translation;
+    let first_cam = test.player_cam_entity();
+    let gamepad = Gamepad(0);
+    let second_start = Vec3::new(3., 0.5, 0.);
+    let second_cam_transform = Transform::from_xyz(0., 2., 5.);
+    let input = PlayerInput {
+        map: PlayerKeyMap {
+            forward: vec![KeyCode::I],
+            ..Default::default()
+        },
+        gamepad: Some(gamepad),
+        ..Default::default()
+    };
+    let (second, second_cam) = spawn_second_player(&mut test, input, second_cam_transform);
+    test.app
+        .world
+        .resource_mut::<Axis<GamepadAxis>>()
+        .set(GamepadAxis(gamepad, GamepadAxisType::LeftStickX), 0.5);
+
+    test.press(KeyCode::I);
+    let elapsed = test.run(10);
+
+    let position = |entity| test.app.world.get::<Transform>(entity).unwrap().translation;
+    assert_near(position(first), first_start);
+    assert_near(
+        position(second),
+        second_start + Vec3::new(0.5, 0., -1.).normalize() * 4. * elapsed,
+    );
+    assert_eq!(test.active_camera(), Some(first_cam));
+    let second_cam = test.app.world.get::<Transform>(second_cam).unwrap();
+    assert_eq!(*second_cam, second_cam_transform);
+}
+
+#[test]
+fn second_player_cam_follows_its_own_mode() {
+    let mut test = TestApp::new(CameraState::FollowBehind);
+    let gamepad = Gamepad(0);
+    let input = PlayerInput {
+        gamepad: Some(gamepad),
+        mode: Some(CameraState::TopDownDirection),
+        ..Default::default()
+    };
+    let (_, second_cam) = spawn_second_player(&mut test, input, Transform::identity());
+    test.step();
+
+    let dist = test.settings().dist;
+    let cam = *test.app.world.get::<Transform>(second_cam).unwrap();
+    assert_near(cam.translation, Vec3::Y * dist);
+    assert!(cam
+        .rotation
+        .abs_diff_eq(Quat::from_rotation_x(-FRAC_PI_2), 1e-5));
+    assert_eq!(test.mode(), CameraState::FollowBehind);
+
+    test.send(GamepadEventRaw(
+        gamepad,
+        GamepadEventType::ButtonChanged(GamepadButtonType::Select, 1.),
+    ));
+    test.step();
+
+    let follow_dist = test.settings().follow_dist;
+    let cam = *test.app.world.get::<Transform>(second_cam).unwrap();
+    assert_near(cam.translation, Vec3::new(0., 0.25, 1.) * follow_dist);
+    assert_eq!(test.mode(), CameraState::FollowBehind);
+}
+
+#[test]
+fn second_player_moves_relative_to_its_own_camera() {
+    let mut test = TestApp::with_resources(CameraState::FollowBehind, |app| {
+        app.insert_resource(PlayerSettings {
+            cam_fwd: true,
+            ..Default::default()
+        });
+    });
+    let input = PlayerInput {
+        map: PlayerKeyMap {
+            forward: vec![KeyCode::I],
+            ..Default::default()
+        },
+        ..Default::default()
+    };
+    // Looking along +X, while the plugin's camera looks along -Z
+    let cam = Transform::from_xyz(0., 2., 0.).looking_at(Vec3::new(1., 2., 0.), Vec3::Y);
+    let (second, _) = spawn_second_player(&mut test, input, cam);
+    test.step();
+
+    test.press(KeyCode::I);
+    let elapsed = test.run(10);
+
+    let speed = test.player_settings().speed;
+    let position = test.app.world.get::<Transform>(second).unwrap().translation;
+    assert_near(position, Vec3::new(3., 0.5, 0.) + Vec3::X * speed * elapsed);
+}
+
+fn grounded() -> TestApp {
+    TestApp::with_resources(CameraState::FollowBehind, |app| {
+        app.insert_resource(PlayerSettings {
+            movement: PlayerMovement::Grounded,
+            ..Default::default()
+        });
+    })
+}
+
+#[test]
+fn grounded_player_accelerates_to_its_top_speed_and_stops() {
+    let mut test = grounded();
+    let speed = test.player_settings().speed;
+    let grounded = test.player_settings().grounded.clone();
+
+    test.press(KeyCode::Up);
+    let elapsed = test.run(2);
+    let velocity = test.player_velocity().velocity;
+    assert_near(velocity, Vec3::Z * -grounded.acceleration * elapsed);
+
+    // Exactly when the top speed is reached
+    test.run_for(speed / grounded.acceleration - elapsed);
+    assert_near(test.player_velocity().velocity, Vec3::new(0., 0., -speed));
+
+    test.release(KeyCode::Up);
+    test.run_for(speed / grounded.deceleration);
+    assert_near(test.player_velocity().velocity, Vec3::ZERO);
+}
+
+#[test]
+fn grounded_player_keeps_its_speed_while_jumping() {
+    let mut test = grounded();
+    let speed = test.player_settings().speed;
+
+    test.press(KeyCode::Up);
+    test.press(KeyCode::RShift);
+    test.run_for(0.6);
+
+    let velocity = test.player_velocity().velocity;
+    assert_near(
+        Vec3::new(velocity.x, 0., velocity.z),
+        Vec3::new(0., 0., -speed),
+    );
+}
+
+#[test]
+fn grounded_player_falls_and_jumps() {
+    let mut test = grounded();
+    let height = test.player_settings().grounded.height;
+    test.set_player(Vec3::new(0., 3., 0.));
+
+    test.run_for(1.5);
+    assert!(test.player_velocity().grounded);
+    assert_near(test.player().translation, Vec3::new(0., height, 0.));
+
+    test.press(KeyCode::RShift);
+    test.run(3);
+    test.release(KeyCode::RShift);
+    assert!(!test.player_velocity().grounded);
+    assert!(test.player().translation.y > height);
+
+    test.run_for(1.5);
+    assert!(test.player_velocity().grounded);
+    assert_near(test.player().translation, Vec3::new(0., height, 0.));
+}
+
+fn vehicle() -> TestApp {
+    TestApp::with_resources(CameraState::FollowBehind, |app| {
+        app.insert_resource(PlayerSettings {
+            movement: PlayerMovement::Vehicle,
+            vehicle: VehicleSettings {
+                bob_height: 0.,
+                ..Default::default()
+            },
+            ..Default::default()
+        });
+    })
+}
+
+#[test]
+fn vehicle_only_steers_while_moving() {
+    let mut test = vehicle();
+    let hover_height = test.player_settings().vehicle.hover_height;
+    let rotation = test.player().rotation;
+
+    test.press(KeyCode::Left);
+    test.run(10);
+    assert!(test.player().rotation.angle_between(rotation) < 1e-3);
+    assert!((test.player().translation.y - hover_height).abs() < 1e-3);
+
+    test.press(KeyCode::Up);
+    test.run_for(0.5);
+    let player_velocity = test.player_velocity();
+    assert!(player_velocity.angular_velocity > 0.);
+    // Rolls its right side up into the left turn
+    assert!(player_velocity.bank < 0.);
+    assert!((test.player().right().y - (-player_velocity.bank).sin()).abs() < 1e-3);
+}
+
+#[test]
+fn vehicle_brakes_and_reverses() {
+    let mut test = vehicle();
+    let speed = test.player_settings().speed;
+    let reverse = test.player_settings().vehicle.reverse;
+
+    test.press(KeyCode::Up);
+    test.run_for(1.);
+    let forward = test.player().forward();
+    assert!(test.player_velocity().velocity.dot(forward) > speed * 0.9);
+
+    test.release(KeyCode::Up);
+    test.press(KeyCode::Down);
+    test.run_for(1.5);
+    let velocity = test.player_velocity().velocity;
+    assert!(velocity.dot(forward) < 0.);
+    assert!(velocity.length() <= speed * reverse + 1e-3);
+}
+
+fn flight(mode: CameraState, auto_level: f32) -> TestApp {
+    TestApp::with_resources(mode, move |app| {
+        app.insert_resource(PlayerSettings {
+            movement: PlayerMovement::Flight,
+            flight: FlightSettings {
+                auto_level,
+                ..Default::default()
+            },
+            ..Default::default()
+        });
+    })
+}
+
+#[test]
+fn flight_player_pitches_and_flies_along_its_nose() {
+    let mut test = flight(CameraState::FollowBehind, 0.);
+
+    test.press(KeyCode::End);
+    test.run_for(0.3);
+    test.release(KeyCode::End);
+    test.run_for(0.3);
+    let forward = test.player().forward();
+    assert!(forward.y > 0.2);
+
+    let from = test.player().translation;
+    test.press(KeyCode::Up);
+    test.run(10);
+    let moved = test.player().translation - from;
+    assert!(moved.normalize().dot(forward) > 0.999);
+}
+
+#[test]
+fn flight_player_rolls_and_levels_out() {
+    let mut test = flight(CameraState::FollowBehind, 5.);
+
+    test.press(KeyCode::Delete);
+    test.run_for(0.3);
+    // Rolls left, its right side goes up
+    assert!(test.player().right().y > 0.2);
+
+    test.release(KeyCode::Delete);
+    test.run_for(2.);
+    assert!(test.player().right().y.abs() < 1e-2);
+}
+
+#[test]
+fn look_at_rolls_with_a_flying_player() {
+    let mut test = flight(CameraState::LookAt, 0.);
+    test.set_player_rotation(Quat::from_rotation_z(FRAC_PI_2));
+
+    test.run(5);
+
+    let player_up = test.player().up();
+    assert!(test.fly_cam().up().dot(player_up) > 0.9);
+}
+
+#[test]
+fn player_turns_at_its_turn_speed() {
+    let mut test = TestApp::new(CameraState::FollowBehind);
+    let turn_speed = test.player_settings().turn_speed;
+    let turn_acceleration = test.player_settings().turn_acceleration;
+
+    test.press(KeyCode::Left);
+    let elapsed = test.run(2);
+    let angular_velocity = test.player_velocity().angular_velocity;
+    assert!((angular_velocity - turn_acceleration * elapsed).abs() < 1e-4);
+
+    // Exactly when the turn speed is reached
+    test.run_for(turn_speed / turn_acceleration - elapsed);
+    assert!((test.player_velocity().angular_velocity - turn_speed).abs() < 1e-4);
+
+    test.release(KeyCode::Left);
+    test.run_for(turn_speed / turn_acceleration);
+    assert!(test.player_velocity().angular_velocity.abs() < 1e-4);
+}
+
+#[test]
+fn turning_keeps_the_pitch_of_the_player_model() {
+    let mut test = TestApp::new(CameraState::FollowBehind);
+    let pitch = Quat::from_rotation_x(-FRAC_PI_2);
+    test.set_player_rotation(pitch);
+
+    test.press(KeyCode::Right);
+    test.run_for(0.2);
+
+    let rotation = test.player().rotation;
+    let (axis, angle) = (rotation * pitch.inverse()).to_axis_angle();
+    assert!(angle > 0.1);
+    assert_near(axis, -Vec3::Y);
+}
+
+fn camera_relative(mode: CameraState) -> TestApp {
+    TestApp::with_resources(mode, |app| {
+        app.insert_resource(PlayerSettings {
+            cam_fwd: true,
+            ..Default::default()
+        });
+    })
+}
+
+#[test]
+fn camera_relative_player_turns_to_face_where_it_moves() {
+    // The top of the top down view is -Z, so its right is +X
+    let mut test = camera_relative(CameraState::TopDown);
+    let start = test.player().translation;
+
+    test.press(KeyCode::Period);
+    let elapsed = test.run_for(1.5);
+
+    let speed = test.player_settings().speed;
+    let player = test.player();
+    assert_near(player.translation, start + Vec3::X * speed * elapsed);
+    assert_near(player.forward(), Vec3::X);
+}
+
+#[test]
+fn camera_relative_player_keeps_its_direction_while_the_camera_turns() {
+    // The follow behind camera turns with the player
+    let mut test = camera_relative(CameraState::FollowBehind);
+    test.step();
+    let start = test.player().translation;
+
+    test.press(KeyCode::Period);
+    let elapsed = test.run(60);
+
+    let speed = test.player_settings().speed;
+    assert_near(test.player().translation, start + Vec3::X * speed * elapsed);
+}
@@ -0,0 +1,237 @@
+//! Headless app for driving `ConfigCam` with synthetic input
+
+#![allow(dead_code)]
+
+use bevy::{
+    asset::AssetPlugin,
+    ecs::event::Events,
+    input::{
+        keyboard::KeyboardInput,
+        mouse::{MouseMotion, MouseScrollUnit, MouseWheel},
+        ElementState, InputPlugin,
+    },
+    prelude::*,
+    render::camera::{ActiveCamera, Camera3d},
+    transform::TransformPlugin,
+};
+use bevy_config_cam::*;
+
+/// Seconds every frame advances the plugin by, through a fixed `CamTime`
+pub const FRAME: f32 = 0.005;
+
+pub struct TestApp {
+    pub app: App,
+}
+
+impl TestApp {
+    /// An app with `ConfigCam` in the given mode, with no window and no renderer
+    pub fn new(mode: CameraState) -> Self {
+        Self::with_resources(mode, |_| {})
+    }
+
+    /// Like `new`, letting resources be inserted before the plugin is added
+    pub fn with_resources(mode: CameraState, insert: impl FnOnce(&mut App)) -> Self {
+        let mut app = App::new();
+        app.add_plugins(MinimalPlugins)
+            .add_plugin(TransformPlugin)
+            .add_plugin(InputPlugin)
+            .add_plugin(AssetPlugin)
+            .add_asset::<Mesh>()
+            .add_asset::<StandardMaterial>()
+            .init_resource::<ActiveCamera<Camera3d>>()
+            .insert_resource(CamController::new(mode))
+            .insert_resource(CamTime::fixed(FRAME));
+        insert(&mut app);
+        app.add_plugin(ConfigCam);
+
+        let mut test = Self { app };
+        // Runs the startup systems and spawns the player and cameras
+        test.app.update();
+        test
+    }
+
+    /// Advances one frame and returns how many seconds it took
+    pub fn step(&mut self) -> f32 {
+        self.app.update();
+        FRAME
+    }
+
+    /// Advances `frames` frames and returns how many seconds they took
+    pub fn run(&mut self, frames: usize) -> f32 {
+        (0..frames).map(|_| self.step()).sum()
+    }
+
+    /// Advances as many frames as fit in `seconds` and returns how many seconds they took
+    pub fn run_for(&mut self, seconds: f32) -> f32 {
+        self.run((seconds / FRAME).round() as usize)
+    }
+
+    pub fn press(&mut self, key: KeyCode) {
+        self.key(key, ElementState::Pressed);
+    }
+
+    pub fn release(&mut self, key: KeyCode) {
+        self.key(key, ElementState::Released);
+    }
+
+    fn key(&mut self, key: KeyCode, state: ElementState) {
+        self.send(KeyboardInput {
+            scan_code: 0,
+            key_code: Some(key),
+            state,
+        });
+    }
+
+    /// Presses and releases `key` over two frames
+    pub fn tap(&mut self, key: KeyCode) {
+        self.press(key);
+        self.step();
+        self.release(key);
+        self.step();
+    }
+
+    pub fn mouse_motion(&mut self, delta: Vec2) {
+        self.send(MouseMotion { delta });
+    }
+
+    pub fn mouse_wheel(&mut self, lines: f32) {
+        self.send(MouseWheel {
+            unit: MouseScrollUnit::Line,
+            x: 0.,
+            y: lines,
+        });
+    }
+
+    /// Scrolls like a trackpad does
+    pub fn mouse_wheel_pixels(&mut self, pixels: f32) {
+        self.send(MouseWheel {
+            unit: MouseScrollUnit::Pixel,
+            x: 0.,
+            y: pixels,
+        });
+    }
+
+    pub fn send<T: Send + Sync + 'static>(&mut self, event: T) {
+        self.app.world.resource_mut::<Events<T>>().send(event);
+    }
+
+    pub fn mode(&self) -> CameraState {
+        self.app.world.resource::<CamController>().mode().clone()
+    }
+
+    pub fn set_mode(&mut self, mode: CameraState) {
+        self.app
+            .world
+            .resource_mut::<CamController>()
+            .set_mode(mode);
+    }
+
+    pub fn settings(&self) -> &MovementSettings {
+        self.app.world.resource::<MovementSettings>()
+    }
+
+    pub fn player_settings(&self) -> &PlayerSettings {
+        self.app.world.resource::<PlayerSettings>()
+    }
+
+    pub fn player_velocity(&mut self) -> PlayerVelocity {
+        let mut query = self
+            .app
+            .world
+            .query_filtered::<&PlayerVelocity, With<PlayerMove>>();
+        *query
+            .iter(&self.app.world)
+            .next()
+            .expect("no player velocity found")
+    }
+
+    pub fn player(&mut self) -> Transform {
+        self.single::<PlayerMove>().1
+    }
+
+    pub fn player_entity(&mut self) -> Entity {
+        self.single::<PlayerMove>().0
+    }
+
+    pub fn set_player(&mut self, translation: Vec3) {
+        let mut query = self
+            .app
+            .world
+            .query_filtered::<&mut Transform, With<PlayerMove>>();
+        for mut transform in query.iter_mut(&mut self.app.world) {
+            transform.translation = translation;
+        }
+    }
+
+    pub fn set_player_rotation(&mut self, rotation: Quat) {
+        let mut query = self
+            .app
+            .world
+            .query_filtered::<&mut Transform, With<PlayerMove>>();
+        for mut transform in query.iter_mut(&mut self.app.world) {
+            transform.rotation = rotation;
+        }
+    }
+
+    /// The plugin's fly cam, leaving out free cameras with their own window
+    pub fn fly_cam(&mut self) -> Transform {
+        let mut query = self
+            .app
+            .world
+            .query_filtered::<&Transform, (With<FlyCam>, Without<CamWindow>)>();
+        *query
+            .iter(&self.app.world)
+            .next()
+            .expect("no fly cam found")
+    }
+
+    /// The player cam's transform relative to the player
+    pub fn player_cam(&mut self) -> Transform {
+        self.single::<PlayerCam>().1
+    }
+
+    pub fn fly_cam_entity(&mut self) -> Entity {
+        self.single::<FlyCam>().0
+    }
+
+    pub fn player_cam_entity(&mut self) -> Entity {
+        self.single::<PlayerCam>().0
+    }
+
+    pub fn photo_mode(&self) -> &PhotoMode {
+        self.app.world.resource::<PhotoMode>()
+    }
+
+    pub fn global_transform(&self, entity: Entity) -> GlobalTransform {
+        *self
+            .app
+            .world
+            .get::<GlobalTransform>(entity)
+            .expect("no global transform found")
+    }
+
+    pub fn active_camera(&self) -> Option<Entity> {
+        self.app.world.resource::<ActiveCamera<Camera3d>>().get()
+    }
+
+    /// Every event of type `T` sent since the app was created
+    pub fn events<T: Clone + Send + Sync + 'static>(&self) -> Vec<T> {
+        let events = self.app.world.resource::<Events<T>>();
+        events.get_reader().iter(events).cloned().collect()
+    }
+
+    fn single<T: Component>(&mut self) -> (Entity, Transform) {
+        let mut query = self
+            .app
+            .world
+            .query_filtered::<(Entity, &Transform), With<T>>();
+        let mut entities = query.iter(&self.app.world);
+        let (entity, transform) = entities.next().expect("no entity found");
+        assert!(entities.next().is_none(), "more than one entity found");
+        (entity, *transform)
+    }
+}
+
+pub fn assert_near(a: Vec3, b: Vec3) {
+    assert!(a.abs_diff_eq(b, 1e-3), "{} is not near {}", a, b);
+}
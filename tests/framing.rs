@@ -0,0 +1,57 @@
+use bevy::prelude::*;
+use bevy_config_cam::*;
+
+// Looking down -Z at a focus 10 units ahead, where the screen is 10 * tan(fov / 2) units tall
+fn view() -> (Transform, PerspectiveProjection, Vec3, f32) {
+    let projection = PerspectiveProjection {
+        aspect_ratio: 1.,
+        ..Default::default()
+    };
+    let half_extent = 10. * (projection.fov * 0.5).tan();
+    (
+        Transform::identity(),
+        projection,
+        Vec3::new(0., 0., -10.),
+        half_extent,
+    )
+}
+
+#[test]
+fn target_inside_the_dead_zone_leaves_the_focus() {
+    let (camera, projection, focus, half_extent) = view();
+    let framing = Framing::default();
+
+    let dead_zone = framing.dead_zone * half_extent;
+    let target = focus + Vec3::new(0.5 * dead_zone.x, -0.5 * dead_zone.y, 0.);
+    let framed = framing.frame(&camera, &projection, focus, target, 1. / 60.);
+
+    assert_eq!(framed, focus);
+}
+
+#[test]
+fn target_beyond_the_soft_zone_is_pulled_back_to_its_edge() {
+    let (camera, projection, focus, half_extent) = view();
+    let framing = Framing {
+        damping: 0.,
+        ..Default::default()
+    };
+
+    let target = focus + Vec3::X * half_extent;
+    let framed = framing.frame(&camera, &projection, focus, target, 1. / 60.);
+
+    let screen = (target - framed).x / half_extent;
+    assert!((screen - framing.soft_zone.x).abs() < 1e-5);
+}
+
+#[test]
+fn catching_up_does_not_depend_on_the_frame_rate() {
+    let (camera, projection, focus, half_extent) = view();
+    let framing = Framing::default();
+    let target = focus + Vec3::X * 0.5 * half_extent;
+
+    let once = framing.frame(&camera, &projection, focus, target, 0.1);
+    let halfway = framing.frame(&camera, &projection, focus, target, 0.05);
+    let twice = framing.frame(&camera, &projection, halfway, target, 0.05);
+
+    assert!(once.abs_diff_eq(twice, 1e-5));
+}
@@ -0,0 +1,107 @@
+mod common;
+
+use bevy::{
+    prelude::*,
+    render::mesh::{Indices, PrimitiveTopology},
+};
+use bevy_config_cam::*;
+use common::*;
+
+fn on_ground(movement: PlayerMovement, ground: Ground) -> TestApp {
+    TestApp::with_resources(CameraState::FollowBehind, |app| {
+        app.insert_resource(PlayerSettings {
+            movement,
+            ..Default::default()
+        })
+        .insert_resource(PlayerGround::new(ground));
+    })
+}
+
+#[test]
+fn grounded_player_walks_up_a_hill() {
+    // Rises towards -Z, the player's forward
+    let hill = |p: Vec2| -0.3 * p.y;
+    let mut test = on_ground(PlayerMovement::Grounded, Ground::from_fn(hill));
+    let height = test.player_settings().grounded.height;
+
+    test.press(KeyCode::Up);
+    test.run_for(1.);
+
+    let player = test.player().translation;
+    assert!(player.z < -1.);
+    assert!(test.player_velocity().grounded);
+    assert!((player.y - (hill(Vec2::new(player.x, player.z)) + height)).abs() < 1e-3);
+}
+
+#[test]
+fn grounded_player_stops_at_a_steep_slope() {
+    // A wall rising at about 70 degrees from z = -1
+    let wall = |p: Vec2| (-p.y - 1.).max(0.) * 3.;
+    let mut test = on_ground(PlayerMovement::Grounded, Ground::from_fn(wall));
+
+    test.press(KeyCode::Up);
+    test.run_for(1.);
+
+    let player = test.player().translation;
+    assert!(player.z < -0.8 && player.z > -1.2, "{}", player);
+    assert!(test.player_velocity().grounded);
+}
+
+#[test]
+fn grounded_player_stands_on_a_mesh() {
+    let plane = Mesh::from(shape::Plane { size: 10. });
+    let ground = Ground::from_mesh(&plane, &Transform::from_xyz(0., 2., 0.)).unwrap();
+    let mut test = on_ground(PlayerMovement::Grounded, ground);
+    let height = test.player_settings().grounded.height;
+
+    test.run_for(0.5);
+
+    assert!(test.player_velocity().grounded);
+    assert_near(test.player().translation, Vec3::new(0., 2. + height, 0.));
+}
+
+#[test]
+fn flying_player_stays_above_the_ground() {
+    let mut test = on_ground(PlayerMovement::Fly, Ground::from_fn(|_| 1.));
+    let height = test.player_settings().grounded.height;
+
+    test.press(KeyCode::Minus);
+    test.run(20);
+
+    assert_near(test.player().translation, Vec3::new(0., 1. + height, 0.));
+}
+
+#[test]
+fn mesh_ground_finds_the_triangle_under_every_point() {
+    // A 16 by 16 quad slope from -8 to 8, spread over many cells of the grid
+    let height = |x: f32, z: f32| 0.25 * x - 0.5 * z;
+    let mut positions = Vec::new();
+    for z in -8..=8 {
+        for x in -8..=8 {
+            positions.push([x as f32, height(x as f32, z as f32), z as f32]);
+        }
+    }
+    let mut indices = Vec::new();
+    for z in 0..16 {
+        for x in 0..16 {
+            let i = z * 17 + x;
+            indices.extend([i, i + 17, i + 1, i + 1, i + 17, i + 18]);
+        }
+    }
+    let mut mesh = Mesh::new(PrimitiveTopology::TriangleList);
+    mesh.insert_attribute(Mesh::ATTRIBUTE_POSITION, positions);
+    mesh.set_indices(Some(Indices::U32(indices)));
+    let ground = Ground::from_mesh(&mesh, &Transform::identity()).unwrap();
+
+    for point in [
+        Vec2::new(-8., -8.),
+        Vec2::new(-3.3, 5.7),
+        Vec2::new(0., 0.),
+        Vec2::new(4.5, -7.9),
+        Vec2::new(8., 8.),
+    ] {
+        let expected = height(point.x, point.y);
+        assert!((ground.height(point).unwrap() - expected).abs() < 1e-3);
+    }
+    assert_eq!(ground.height(Vec2::new(8.5, 0.)), None);
+}
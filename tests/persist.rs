@@ -0,0 +1,63 @@
+#![cfg(feature = "serde")]
+
+mod common;
+
+use bevy::{app::AppExit, prelude::*};
+use bevy_config_cam::*;
+use common::*;
+
+fn saving_to(name: &str) -> (TestApp, std::path::PathBuf) {
+    let path = std::env::temp_dir().join(name);
+    let _ = std::fs::remove_file(&path);
+    let file = CamConfigFile {
+        path: path.clone(),
+        save_delay: 0.2,
+        ..Default::default()
+    };
+    let test = TestApp::with_resources(CameraState::FollowBehind, |app| {
+        app.insert_resource(file);
+    });
+    (test, path)
+}
+
+#[test]
+fn config_is_saved_once_the_changes_settle() {
+    let (mut test, path) = saving_to("bevy_config_cam_settle.ron");
+
+    test.mouse_wheel(1.);
+    test.run(5);
+    test.mouse_wheel(1.);
+    test.step();
+    assert!(!path.exists());
+
+    test.run_for(0.5);
+    assert!(path.exists());
+    let _ = std::fs::remove_file(path);
+}
+
+#[test]
+fn config_is_saved_on_exit() {
+    let (mut test, path) = saving_to("bevy_config_cam_exit.ron");
+
+    test.mouse_wheel(1.);
+    test.step();
+    test.send(AppExit);
+    test.step();
+
+    assert!(path.exists());
+    let _ = std::fs::remove_file(path);
+}
+
+#[test]
+fn config_keeps_the_mode_the_photo_mode_returns_to() {
+    let (mut test, path) = saving_to("bevy_config_cam_photo.ron");
+
+    test.tap(KeyCode::P);
+    test.run_for(0.5);
+    test.send(AppExit);
+    test.step();
+
+    let config = CamConfig::load(&path).unwrap();
+    assert_eq!(config.mode, CameraState::FollowBehind);
+    let _ = std::fs::remove_file(path);
+}
@@ -0,0 +1,105 @@
+mod common;
+
+use bevy::prelude::*;
+use bevy_config_cam::*;
+use common::*;
+
+#[test]
+fn photo_mode_starts_from_the_view_and_restores_it() {
+    let mut test = TestApp::new(CameraState::FollowBehind);
+    test.run(5);
+    let fly_cam = test.fly_cam();
+    let player_cam = test.player_cam_entity();
+    let view = test.global_transform(player_cam);
+
+    test.tap(KeyCode::P);
+    assert!(test.photo_mode().active());
+    assert_eq!(test.mode(), CameraState::Free);
+    assert_near(test.fly_cam().translation, view.translation);
+    assert!(test.fly_cam().rotation.angle_between(view.rotation) < 1e-3);
+
+    // Gameplay is frozen, the player stays put
+    let player = test.player().translation;
+    test.press(KeyCode::Up);
+    test.run(5);
+    test.release(KeyCode::Up);
+    assert!(test.photo_mode().frozen());
+    assert_near(test.player().translation, player);
+
+    test.send(TogglePhotoMode);
+    test.step();
+    assert!(!test.photo_mode().active());
+    assert_eq!(test.mode(), CameraState::FollowBehind);
+    assert_near(test.fly_cam().translation, fly_cam.translation);
+    assert!(test.fly_cam().rotation.angle_between(fly_cam.rotation) < 1e-3);
+}
+
+#[test]
+fn photo_camera_rolls_and_keeps_away_from_the_player() {
+    let mut test = TestApp::new(CameraState::Free);
+    let radius = test.settings().photo.collision_radius;
+    test.tap(KeyCode::P);
+
+    test.press(KeyCode::Q);
+    test.run(10);
+    test.release(KeyCode::Q);
+    let fly_cam = test.fly_cam_entity();
+    assert!(test.app.world.get::<PhotoParams>(fly_cam).unwrap().roll > 0.);
+    // Rolls left, its right side goes up
+    assert!(test.fly_cam().right().y > 0.);
+
+    let player = test.player().translation;
+    test.app
+        .world
+        .get_mut::<Transform>(fly_cam)
+        .unwrap()
+        .translation = player + Vec3::new(0., 0.2, 0.);
+    test.step();
+    assert!(test.fly_cam().translation.distance(player) >= radius - 1e-3);
+}
+
+#[test]
+fn photo_mode_hides_and_shows_the_ui() {
+    let mut test = TestApp::new(CameraState::Free);
+    let ui = test
+        .app
+        .world
+        .spawn()
+        .insert(Visibility::default())
+        .insert(HideInPhotoMode)
+        .id();
+    let visible = |test: &TestApp| test.app.world.get::<Visibility>(ui).unwrap().is_visible;
+
+    test.tap(KeyCode::P);
+    assert!(!visible(&test));
+
+    test.tap(KeyCode::H);
+    assert!(visible(&test));
+    test.tap(KeyCode::H);
+    assert!(!visible(&test));
+
+    test.tap(KeyCode::P);
+    assert!(visible(&test));
+}
+
+#[test]
+fn presets_wait_for_the_photo_mode_to_end() {
+    let mut test = TestApp::with_resources(CameraState::FollowBehind, |app| {
+        app.insert_resource(CamPresets {
+            presets: vec![CamPreset {
+                keys: vec![KeyCode::Key1],
+                ..CamPreset::new("top", CameraState::TopDown)
+            }],
+            blend: 0.,
+        });
+    });
+
+    test.tap(KeyCode::P);
+    test.tap(KeyCode::Key1);
+    test.send(SwitchCamPreset("top".into()));
+    test.step();
+    assert_eq!(test.mode(), CameraState::Free);
+
+    test.tap(KeyCode::P);
+    assert_eq!(test.mode(), CameraState::FollowBehind);
+}
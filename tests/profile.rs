@@ -0,0 +1,35 @@
+#![cfg(feature = "serde")]
+
+mod common;
+
+use bevy::prelude::*;
+use bevy_config_cam::*;
+use common::*;
+
+#[test]
+fn profile_distances_win_over_an_earlier_zoom() {
+    let mut test = TestApp::new(CameraState::FollowBehind);
+    test.app
+        .world
+        .resource_mut::<CamController>()
+        .set_scroll_type(ScrollType::Zoom);
+    test.mouse_wheel(3.);
+    test.run(5);
+
+    let profile = CamProfile {
+        movement: MovementSettings {
+            follow_dist: 7.,
+            ..Default::default()
+        },
+        ..Default::default()
+    };
+    let handle = test
+        .app
+        .world
+        .resource_mut::<Assets<CamProfile>>()
+        .add(profile);
+    test.app.world.insert_resource(ActiveCamProfile(handle));
+    test.run(60);
+
+    assert!((test.settings().follow_dist - 7.).abs() < 1e-3);
+}